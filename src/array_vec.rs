@@ -0,0 +1,411 @@
+use crate::error::{check_index, Error};
+use crate::slices::{SliceBacked, SliceBackedMut, SliceIterMut, SliceRead, SliceWrite};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+/// Inline storage of up to `N` items, with a runtime length (like
+/// `arrayvec::ArrayVec`). Unlike the Array variant of `SliceStorage*` (which
+/// always has exactly `N` items), it can grow and shrink - without heap, so
+/// it works under `no_std` without `no_std_heap`, too.
+///
+/// Unused slots are uninitialized, so `T` doesn't need to implement
+/// `Default`. Items that are removed get dropped straight away.
+///
+/// Growing operations panic (or their `try_*` counterparts return
+/// `Error::CapacityExceeded`) if they would exceed `N` items.
+pub struct ArrayVecStorage<T, const N: usize> {
+    /// Invariant: `slots.start` is 0.
+    slots: Slots<T, [MaybeUninit<T>; N]>,
+}
+
+/// Slots `items` (an array of `MaybeUninit<T>`), of which `start..end` are
+/// initialized. It drops those.
+///
+/// Its `Drop` implementation has no const generic parameter, and it lets
+/// both `T` and `A` dangle. That matters, because `SliceStorage*` embed
+/// `ArrayVecStorage` (as their Inline variant), and packed storages embed
+/// `SliceStorage*` with a size computed from their own const generic
+/// parameter. Under `generic_const_exprs` such a size captures all the
+/// generic parameters of the packed storage, including its lifetime. So a
+/// `Drop` implementation for `ArrayVecStorage<T, N>` itself would make
+/// views like `PackedBoolStorage::from_mutable_bytes` hold their borrow
+/// until the end of scope.
+struct Slots<T, A> {
+    items: A,
+    start: usize,
+    end: usize,
+    /// We own (and drop) `T` items.
+    item: PhantomData<T>,
+}
+
+impl<T, const N: usize> Slots<T, [MaybeUninit<T>; N]> {
+    const fn new() -> Self {
+        Self {
+            items: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            end: 0,
+            item: PhantomData,
+        }
+    }
+}
+
+// SAFETY: `drop` doesn't access any `T` other than by dropping the
+// initialized ones (which `PhantomData<T>` tells dropck about), and it
+// doesn't access `A` other than through those.
+unsafe impl<#[may_dangle] T, #[may_dangle] A> Drop for Slots<T, A> {
+    fn drop(&mut self) {
+        // `A` is `[MaybeUninit<T>; N]`, which has the same layout as `[T; N]`.
+        let first = ptr::from_mut(&mut self.items).cast::<T>();
+        // SAFETY: Slots `start..end` are initialized, and they won't be
+        // accessed again.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                first.add(self.start),
+                self.end - self.start,
+            ))
+        };
+    }
+}
+
+impl<T, const N: usize> ArrayVecStorage<T, N> {
+    /// New empty instance.
+    pub const fn new() -> Self {
+        Self {
+            slots: Slots::new(),
+        }
+    }
+
+    /// Collect all of `iter`.
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, Error> {
+        let mut storage = Self::new();
+        for item in iter {
+            storage.try_push(item)?;
+        }
+        Ok(storage)
+    }
+
+    /// Maximum number of items, that is, `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    pub fn is_full(&self) -> bool {
+        self.slots.end == N
+    }
+
+    /// Return `Error::CapacityExceeded` unless there is space for
+    /// `additional` more items.
+    fn check_capacity(&self, additional: usize) -> Result<(), Error> {
+        if additional <= N - self.slots.end {
+            Ok(())
+        } else {
+            Err(Error::CapacityExceeded { capacity: N })
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.try_push(item)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    pub fn try_push(&mut self, item: T) -> Result<(), Error> {
+        self.check_capacity(1)?;
+        self.slots.items[self.slots.end].write(item);
+        self.slots.end += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.slots.end == 0 {
+            return None;
+        }
+        self.slots.end -= 1;
+        // SAFETY: The slot was within `len()`, so it's initialized. Now it's
+        // outside, so it won't be read or dropped again.
+        Some(unsafe { self.slots.items[self.slots.end].assume_init_read() })
+    }
+
+    /// Insert at `index`, which may equal `len()`.
+    pub fn insert(&mut self, index: usize, item: T) {
+        self.try_insert(index, item)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    pub fn try_insert(&mut self, index: usize, item: T) -> Result<(), Error> {
+        let len = self.slots.end;
+        if index > len {
+            return Err(Error::IndexOutOfBounds { index, len });
+        }
+        self.check_capacity(1)?;
+        self.slots.items[len].write(item);
+        self.slots.items[index..=len].rotate_right(1);
+        self.slots.end += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.try_remove(index)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    pub fn try_remove(&mut self, index: usize) -> Result<T, Error> {
+        check_index(index, self.slots.end)?;
+        self.slots.items[index..self.slots.end].rotate_left(1);
+        Ok(self.pop().unwrap())
+    }
+
+    /// Keep the first `len` items (if there are more).
+    pub fn truncate(&mut self, len: usize) {
+        while self.slots.end > len {
+            self.slots.end -= 1;
+            // SAFETY: As in `pop`.
+            unsafe { self.slots.items[self.slots.end].assume_init_drop() };
+        }
+    }
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<T: Clone, const N: usize> ArrayVecStorage<T, N> {
+    /// New instance with a copy of `slice`.
+    pub fn from_slice(slice: &[T]) -> Self {
+        Self::try_from_slice(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    pub fn try_from_slice(slice: &[T]) -> Result<Self, Error> {
+        let mut storage = Self::new();
+        storage.try_extend_from_slice(slice)?;
+        Ok(storage)
+    }
+
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        self.try_resize(new_len, value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), Error> {
+        if new_len > self.slots.end {
+            self.check_capacity(new_len - self.slots.end)?;
+            while self.slots.end < new_len {
+                self.slots.items[self.slots.end].write(value.clone());
+                self.slots.end += 1;
+            }
+        } else {
+            self.truncate(new_len);
+        }
+        Ok(())
+    }
+
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.try_extend_from_slice(other)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `extend_from_slice`. If `other` doesn't fit, leave `self`
+    /// unchanged.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), Error> {
+        self.check_capacity(other.len())?;
+        for item in other {
+            self.slots.items[self.slots.end].write(item.clone());
+            self.slots.end += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> Default for ArrayVecStorage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayVecStorage<T, N> {
+    fn clone(&self) -> Self {
+        Self::from_slice(self)
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayVecStorage<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.deref()).finish()
+    }
+}
+
+/// Content-based: unused slots don't matter.
+impl<T: PartialEq, const N: usize, const M: usize> PartialEq<ArrayVecStorage<T, M>>
+    for ArrayVecStorage<T, N>
+{
+    fn eq(&self, other: &ArrayVecStorage<T, M>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVecStorage<T, N> {}
+
+impl<T, const N: usize> Deref for ArrayVecStorage<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: The first `len` slots are initialized, and `MaybeUninit<T>`
+        // has the same layout as `T`.
+        unsafe { core::slice::from_raw_parts(self.slots.items.as_ptr().cast(), self.slots.end) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVecStorage<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: As in `deref`.
+        unsafe {
+            core::slice::from_raw_parts_mut(self.slots.items.as_mut_ptr().cast(), self.slots.end)
+        }
+    }
+}
+
+impl<'s, T, const N: usize> IntoIterator for &'s ArrayVecStorage<T, N> {
+    type Item = &'s T;
+    type IntoIter = core::slice::Iter<'s, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().iter()
+    }
+}
+
+impl<'s, T, const N: usize> IntoIterator for &'s mut ArrayVecStorage<T, N> {
+    type Item = &'s mut T;
+    type IntoIter = core::slice::IterMut<'s, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+/// Owning iterator over items of `ArrayVecStorage`.
+pub struct ArrayVecIntoIter<T, const N: usize> {
+    /// Items not yet returned.
+    slots: Slots<T, [MaybeUninit<T>; N]>,
+}
+
+impl<T, const N: usize> IntoIterator for ArrayVecStorage<T, N> {
+    type Item = T;
+    type IntoIter = ArrayVecIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayVecIntoIter { slots: self.slots }
+    }
+}
+
+impl<T, const N: usize> ArrayVecIntoIter<T, N> {
+    fn as_slice(&self) -> &[T] {
+        let slots = &self.slots;
+        // SAFETY: As in `ArrayVecStorage::deref`.
+        unsafe {
+            core::slice::from_raw_parts(
+                slots.items.as_ptr().add(slots.start).cast(),
+                slots.end - slots.start,
+            )
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for ArrayVecIntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let slots = &mut self.slots;
+        if slots.start == slots.end {
+            return None;
+        }
+        slots.start += 1;
+        // SAFETY: As in `ArrayVecStorage::pop`.
+        Some(unsafe { slots.items[slots.start - 1].assume_init_read() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slots.end - self.slots.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for ArrayVecIntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        let slots = &mut self.slots;
+        if slots.start == slots.end {
+            return None;
+        }
+        slots.end -= 1;
+        // SAFETY: As in `ArrayVecStorage::pop`.
+        Some(unsafe { slots.items[slots.end].assume_init_read() })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for ArrayVecIntoIter<T, N> {}
+impl<T, const N: usize> FusedIterator for ArrayVecIntoIter<T, N> {}
+
+impl<T: Clone, const N: usize> Clone for ArrayVecIntoIter<T, N> {
+    fn clone(&self) -> Self {
+        ArrayVecStorage::from_slice(self.as_slice()).into_iter()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for ArrayVecIntoIter<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayVecIntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T: Clone + PartialEq, const N: usize> SliceRead<T> for ArrayVecStorage<T, N> {
+    fn len(&self) -> usize {
+        self.slots.end
+    }
+
+    fn get(&self, index: usize) -> T {
+        self.shared_slice()[index].clone()
+    }
+    fn iter<'i>(&'i self) -> Self::ITER<'i> {
+        self.shared_slice().iter()
+    }
+
+    fn try_shared_slice(&self) -> Result<&[T], Error> {
+        Ok(self.shared_slice())
+    }
+}
+
+impl<T: Clone + PartialEq, const N: usize> SliceBacked<T> for ArrayVecStorage<T, N> {
+    fn shared_slice(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Clone + PartialEq, const N: usize> SliceWrite<T> for ArrayVecStorage<T, N> {
+    fn check_and_set(&mut self, index: usize, value: &T) -> bool {
+        let item = &mut self.mutable_slice()[index];
+        let is_modifying = *value != *item;
+        *item = value.clone();
+        is_modifying
+    }
+    fn set(&mut self, index: usize, value: &T) {
+        self.mutable_slice()[index] = value.clone();
+    }
+
+    fn try_mutable_slice(&mut self) -> Result<&mut [T], Error> {
+        Ok(self.mutable_slice())
+    }
+}
+
+impl<T: Clone + PartialEq, const N: usize> SliceBackedMut<T> for ArrayVecStorage<T, N> {
+    fn mutable_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Clone + PartialEq, const N: usize> SliceIterMut<T> for ArrayVecStorage<T, N> {
+    type ITER_MUT<'i>
+        = core::slice::IterMut<'i, T>
+    where
+        T: 'i,
+        Self: 'i;
+
+    fn try_iter_mut(&mut self) -> Result<Self::ITER_MUT<'_>, Error> {
+        Ok(self.mutable_slice().iter_mut())
+    }
+}
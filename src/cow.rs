@@ -0,0 +1,102 @@
+use crate::error::{check_index, Error};
+use crate::slices::{SliceBacked, SliceBackedMut, SliceMakeMut, SliceRead, SliceWrite};
+use core::ops::Deref;
+
+/// Copy-on-write wrapper around a `SliceStorage*` (or any other
+/// `SliceMakeMut` implementation). Like `alloc::borrow::Cow<[T]>`: it can
+/// start out borrowing (Shared-based), and it copies the items into an owned
+/// (Array-based or Vec-based) storage on the first modification - `set`,
+/// `check_and_set`, `mutable_slice` or their `try_*` counterparts. So the
+/// choice of ownership doesn't need to be made up front.
+///
+/// It dereferences to the wrapped storage, so you can check which variant
+/// is current.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CowStorage<S> {
+    storage: S,
+}
+
+impl<S> CowStorage<S> {
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+impl<S> Deref for CowStorage<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.storage
+    }
+}
+
+impl<T: Clone + PartialEq, S: SliceMakeMut<T>> SliceRead<T> for CowStorage<S> {
+    type ITER<'i>
+        = S::ITER<'i>
+    where
+        T: 'i,
+        Self: 'i;
+
+    fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    fn get(&self, index: usize) -> T {
+        self.storage.get(index)
+    }
+    fn try_get(&self, index: usize) -> Result<T, Error> {
+        self.storage.try_get(index)
+    }
+    fn iter(&self) -> Self::ITER<'_> {
+        self.storage.iter()
+    }
+
+    fn try_shared_slice(&self) -> Result<&[T], Error> {
+        self.storage.try_shared_slice()
+    }
+}
+
+impl<T: Clone + PartialEq, S: SliceMakeMut<T>> SliceBacked<T> for CowStorage<S> {
+    fn shared_slice(&self) -> &[T] {
+        self.storage.shared_slice()
+    }
+}
+
+impl<T: Clone + PartialEq, S: SliceMakeMut<T>> SliceWrite<T> for CowStorage<S> {
+    fn check_and_set(&mut self, index: usize, value: &T) -> bool {
+        self.try_check_and_set(index, value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Copy only if `value` differs from the current one.
+    fn try_check_and_set(&mut self, index: usize, value: &T) -> Result<bool, Error> {
+        check_index(index, self.len())?;
+        if self.storage.get(index) == *value {
+            return Ok(false);
+        }
+        self.storage.try_make_mut()?[index] = value.clone();
+        Ok(true)
+    }
+    fn set(&mut self, index: usize, value: &T) {
+        self.try_set(index, value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_set(&mut self, index: usize, value: &T) -> Result<(), Error> {
+        check_index(index, self.len())?;
+        self.storage.try_make_mut()?[index] = value.clone();
+        Ok(())
+    }
+
+    fn try_mutable_slice(&mut self) -> Result<&mut [T], Error> {
+        self.storage.try_make_mut()
+    }
+}
+
+impl<T: Clone + PartialEq, S: SliceMakeMut<T>> SliceBackedMut<T> for CowStorage<S> {
+    fn mutable_slice(&mut self) -> &mut [T] {
+        self.storage.make_mut()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::slices::SliceBackedChoice;
+use core::fmt;
+use core::ops::{Bound, Range, RangeBounds};
+
+/// Error returned by the fallible (`try_*`) counterparts of methods of
+/// `crate::slices::Slice` and similar traits. Their non-`try_*` methods panic
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    /// A range that doesn't fit in `len` items, or that ends before it
+    /// starts.
+    RangeOutOfBounds {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+    /// Modifying a Shared-based instance.
+    SharedNotMutable,
+    /// `mutable_vec()` and similar on other than Vec-based or VecRef-based
+    /// instances.
+    NotVecBased,
+    /// The implementation doesn't store a native slice of items (for example,
+    /// it packs or compresses them).
+    NotSliceBacked,
+    /// An iterator (or a function) didn't provide enough items for an
+    /// array-based instance.
+    NotEnoughItems {
+        expected: usize,
+        actual: usize,
+    },
+    /// The source has a different length than the target (for example, than
+    /// the array size `N`).
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// Constructors that populate a new instance can create owned
+    /// (Array-based or Vec-based) instances only.
+    UnsupportedChoice(SliceBackedChoice),
+    /// A value wider than the packed item (see
+    /// `crate::packed_uint::PackedUintStorage`).
+    ValueDoesNotFit {
+        value: u64,
+        bits: usize,
+    },
+    /// Growing a fixed-capacity instance (see
+    /// `crate::array_vec::ArrayVecStorage`) beyond its capacity.
+    CapacityExceeded {
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOutOfBounds { index, len } => {
+                write!(f, "Index {index} out of range for {len} items.")
+            }
+            Self::RangeOutOfBounds { start, end, len } => {
+                write!(f, "Range {start}..{end} out of range for {len} items.")
+            }
+            Self::SharedNotMutable => write!(f, "Can't modify a shared slice."),
+            Self::NotVecBased => write!(f, "Works for Vec and VecRef only."),
+            Self::NotSliceBacked => write!(f, "Not backed by a native slice."),
+            Self::NotEnoughItems { expected, actual } => {
+                write!(f, "Expecting {expected} items, but got {actual} only.")
+            }
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "Expecting {expected} items, but got {actual}.")
+            }
+            Self::UnsupportedChoice(choice) => {
+                write!(f, "Can't populate a new {choice:?}-based instance.")
+            }
+            Self::ValueDoesNotFit { value, bits } => {
+                write!(f, "Value {value} doesn't fit in {bits} bits.")
+            }
+            Self::CapacityExceeded { capacity } => {
+                write!(f, "Can't exceed capacity of {capacity} items.")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Return `Error::IndexOutOfBounds` unless `index < len`.
+pub(crate) fn check_index(index: usize, len: usize) -> Result<(), Error> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(Error::IndexOutOfBounds { index, len })
+    }
+}
+
+/// Resolve `range` to a `Range`. Return `Error::RangeOutOfBounds` unless it
+/// fits in `len` items.
+pub(crate) fn check_range(
+    range: impl RangeBounds<usize>,
+    len: usize,
+) -> Result<Range<usize>, Error> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start <= end && end <= len {
+        Ok(start..end)
+    } else {
+        Err(Error::RangeOutOfBounds { start, end, len })
+    }
+}
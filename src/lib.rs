@@ -18,14 +18,28 @@
 #![feature(generic_const_exprs)]
 #![feature(const_option)]
 #![feature(const_option_ext)]
+#![feature(portable_simd)]
+#![feature(dropck_eyepatch)]
 #![feature(macro_metavar_expr)] // for https://veykril.github.io/tlborm/decl-macros/macros-methodical.html#metavariable-expressions
 
 pub mod abstra;
 pub mod index;
 
+pub mod array_vec;
 pub mod byte_slice;
+pub mod cow;
+pub mod error;
+pub mod packed_bool;
+pub mod packed_uint;
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+pub mod roaring;
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+pub mod run_length;
+pub mod simd;
 pub mod slices;
 
+pub use error::Error;
+
 #[cfg(all(feature = "no_std", feature = "std"))]
 compile_error!("std and no_std are mutually exclusive! Use maximum one of those two.");
 
@@ -0,0 +1,1296 @@
+use crate::abstra::NewEmptyLike;
+use crate::error::{self, Error};
+use crate::simd::{BitwiseOp, SimdBits};
+use crate::slices::{
+    BoolSlice, SliceBacked, SliceBackedChoice, SliceBackedMut, SliceDefault, SliceIterMut,
+    SliceRead, SliceStorageDefault, SliceWrite,
+};
+use crate::{match_cfg, with_heap};
+use core::cell::Cell;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Deref, DerefMut, Not,
+};
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::vec::Vec;
+
+/// Concurrent (atomic) counterpart of `PackedBoolStorage`.
+pub mod atomic;
+/// Operations over a range of bits. They handle whole blocks at once.
+pub mod range;
+/// Population count, rank and select, plus an optional precomputed directory
+/// (`RankSelectIndex`) for large immutable bitsets.
+pub mod rank_select;
+/// Iterate over indices of set/clear bits, and search for next/previous ones.
+pub mod search;
+
+/// Given `num_bits` and `block_bits` (number of bits per block), return number
+/// of blocks required to cover all those bits.
+pub const fn num_bits_to_blocks(num_bits: usize, block_bits: usize) -> usize {
+    let divided = num_bits / block_bits;
+    if num_bits == divided * block_bits {
+        divided
+    } else {
+        divided + 1
+    }
+}
+
+/// A primitive unsigned integer used as a block ("word") of bits in
+/// `PackedBoolStorage`. Wider blocks give native-width bulk operations (and
+/// all block types get `crate::simd` fast paths).
+pub trait BitBlock:
+    SimdBits
+    + Default
+    + PartialEq
+    + Eq
+    + Debug
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + BitAndAssign
+    + BitOrAssign
+    + BitXorAssign
+{
+    /// Number of bits in one block. (Not called `BITS`, so that it doesn't
+    /// clash with inherent `u8::BITS` etc, which are `u32`.)
+    const BLOCK_BITS: usize;
+    const ZERO: Self;
+    /// All bits set.
+    const ONES: Self;
+
+    /// Return 1, shifted by `shift` places to left.
+    fn one_shifted(shift: usize) -> Self;
+    /// Return a block with the lowest `num_bits` bits set. `num_bits` can be
+    /// anything from 0 to `BLOCK_BITS` (inclusive).
+    fn low_mask(num_bits: usize) -> Self;
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    /// Return `self` with its lowest set bit (if any) cleared.
+    fn without_lowest_one(self) -> Self;
+}
+
+macro_rules! bit_block_impl {
+    ($($block:ty),+) => {
+        $(
+            impl BitBlock for $block {
+                const BLOCK_BITS: usize = <$block>::BITS as usize;
+                const ZERO: Self = 0;
+                const ONES: Self = <$block>::MAX;
+
+                #[inline]
+                fn one_shifted(shift: usize) -> Self {
+                    1 << shift
+                }
+                #[inline]
+                fn low_mask(num_bits: usize) -> Self {
+                    if num_bits >= Self::BLOCK_BITS {
+                        Self::ONES
+                    } else {
+                        (1 << num_bits) - 1
+                    }
+                }
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$block>::count_ones(self)
+                }
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$block>::trailing_zeros(self)
+                }
+                #[inline]
+                fn leading_zeros(self) -> u32 {
+                    <$block>::leading_zeros(self)
+                }
+                #[inline]
+                fn without_lowest_one(self) -> Self {
+                    self & self.wrapping_sub(1)
+                }
+            }
+        )+
+    };
+}
+bit_block_impl!(u8, u16, u32, u64, usize);
+
+/// Order of bits within each block of `PackedBoolStorage`. It maps a bit's
+/// (logical) subindex within a block to its physical position. Implemented by
+/// marker types `Lsb0` and `Msb0`.
+pub trait BitOrder: Clone + Copy + Debug + Default + PartialEq + Eq {
+    /// Mask of a bit at (logical) `bit_subindex`.
+    fn bit_mask<B: BitBlock>(bit_subindex: usize) -> B;
+    /// Mask of bits at (logical) subindexes `0..num_bits`. `num_bits` can be
+    /// anything from 0 to `B::BLOCK_BITS` (inclusive).
+    fn prefix_mask<B: BitBlock>(num_bits: usize) -> B;
+    /// (Logical) subindex of the first set bit of `block`. `B::BLOCK_BITS` if
+    /// `block` is zero.
+    fn first_one<B: BitBlock>(block: B) -> usize;
+    /// (Logical) subindex of the last set bit of `block`, which must not be
+    /// zero.
+    fn last_one<B: BitBlock>(block: B) -> usize;
+    /// Return `block` with its first (logical) set bit cleared.
+    fn without_first_one<B: BitBlock>(block: B) -> B;
+}
+
+/// Least significant bit first: Bit with subindex 0 is the least significant
+/// bit of its block. Like `u8`-based bitmaps in most software.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lsb0;
+
+/// Most significant bit first: Bit with subindex 0 is the most significant bit
+/// of its block. Common in wire formats and hardware registers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Msb0;
+
+impl BitOrder for Lsb0 {
+    #[inline]
+    fn bit_mask<B: BitBlock>(bit_subindex: usize) -> B {
+        B::one_shifted(bit_subindex)
+    }
+    #[inline]
+    fn prefix_mask<B: BitBlock>(num_bits: usize) -> B {
+        B::low_mask(num_bits)
+    }
+    #[inline]
+    fn first_one<B: BitBlock>(block: B) -> usize {
+        block.trailing_zeros() as usize
+    }
+    #[inline]
+    fn last_one<B: BitBlock>(block: B) -> usize {
+        B::BLOCK_BITS - 1 - block.leading_zeros() as usize
+    }
+    #[inline]
+    fn without_first_one<B: BitBlock>(block: B) -> B {
+        block.without_lowest_one()
+    }
+}
+
+impl BitOrder for Msb0 {
+    #[inline]
+    fn bit_mask<B: BitBlock>(bit_subindex: usize) -> B {
+        B::one_shifted(B::BLOCK_BITS - 1 - bit_subindex)
+    }
+    #[inline]
+    fn prefix_mask<B: BitBlock>(num_bits: usize) -> B {
+        !B::low_mask(B::BLOCK_BITS - num_bits)
+    }
+    #[inline]
+    fn first_one<B: BitBlock>(block: B) -> usize {
+        block.leading_zeros() as usize
+    }
+    #[inline]
+    fn last_one<B: BitBlock>(block: B) -> usize {
+        B::BLOCK_BITS - 1 - block.trailing_zeros() as usize
+    }
+    #[inline]
+    fn without_first_one<B: BitBlock>(block: B) -> B {
+        block & !Self::bit_mask::<B>(Self::first_one(block))
+    }
+}
+
+/// "Packed" bit storage. Store and access bits in block-based backing storage,
+/// where a block is any `BitBlock` (`u8`, `u16`, `u32`, `u64` or `usize`).
+/// Order of bits within a block is given by `O` (least significant bit first by
+/// default). See `crate::slices::SliceStorage` for const generic parameter `N`
+/// (here it's the number of bits for array-based instances).
+#[derive(Clone)]
+pub struct PackedBoolStorage<'a, B: BitBlock, const N: usize, O: BitOrder = Lsb0>
+where
+    Self: 'a,
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    blocks: SliceStorageDefault<'a, B, { num_bits_to_blocks(N, B::BLOCK_BITS) }>,
+    /// Logical number of bits. Equal to `N` if array-based (except for copies
+    /// of fewer bits made without heap, which leave the rest of the array
+    /// unused). Any bits in the
+    /// last block beyond `num_bits` are padding. They are clear, unless this
+    /// is a view of external blocks (see `from_shared_blocks`). Either way
+    /// they are masked out when read, and they are kept unchanged when bits
+    /// are modified.
+    num_bits: usize,
+    bit_order: PhantomData<O>,
+}
+
+fn get_bit<B: BitBlock, O: BitOrder>(block: B, bit_subindex: usize) -> bool {
+    (block & O::bit_mask::<B>(bit_subindex)) != B::ZERO
+}
+
+/// Pack bits from `bits` into `blocks`, in order `O`. Consume
+/// (up to) `blocks.len() * B::BLOCK_BITS` items. Return the number of bits
+/// packed. Any bits not covered by `bits` are cleared.
+fn pack_bits<B: BitBlock, O: BitOrder>(
+    blocks: &mut [B],
+    bits: impl Iterator<Item = bool>,
+) -> usize {
+    blocks.fill(B::ZERO);
+    let mut num_bits = 0;
+    for (index, bit) in bits.take(blocks.len() * B::BLOCK_BITS).enumerate() {
+        if bit {
+            blocks[index / B::BLOCK_BITS] |= O::bit_mask::<B>(index % B::BLOCK_BITS);
+        }
+        num_bits = index + 1;
+    }
+    num_bits
+}
+
+with_heap! {
+    /// Like `pack_bits`, but collect (all of) `bits` into a new `Vec`.
+    fn pack_bits_to_vec<B: BitBlock, O: BitOrder>(bits: impl Iterator<Item = bool>) -> Vec<B> {
+        let mut vec = Vec::new();
+        for (index, bit) in bits.enumerate() {
+            let bit_subindex = index % B::BLOCK_BITS;
+            if bit_subindex == 0 {
+                vec.push(B::ZERO);
+            }
+            if bit {
+                *vec.last_mut().unwrap() |= O::bit_mask::<B>(bit_subindex);
+            }
+        }
+        vec
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    pub fn from_default_to_array() -> Self {
+        Self {
+            blocks: SliceStorageDefault::from_default_to_array(),
+            num_bits: N,
+            bit_order: PhantomData,
+        }
+    }
+
+    /// View `blocks` as `num_bits` packed bits, without copying. Bits are laid
+    /// out as per `O`, within blocks in native byte order. Any extra blocks,
+    /// and any padding bits in the last used block, are left untouched. Panic
+    /// if `blocks` are too short.
+    pub fn from_shared_blocks(blocks: &'a [B], num_bits: usize) -> Self {
+        Self::check_enough_blocks(blocks.len(), num_bits);
+        Self {
+            blocks: SliceStorageDefault::Shared(blocks),
+            num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    /// Like `from_shared_blocks`, but modifiable in place.
+    pub fn from_mutable_blocks(blocks: &'a mut [B], num_bits: usize) -> Self {
+        Self::check_enough_blocks(blocks.len(), num_bits);
+        Self {
+            blocks: SliceStorageDefault::Mutable(blocks),
+            num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    fn check_enough_blocks(num_blocks: usize, num_bits: usize) {
+        assert!(
+            num_blocks >= num_bits_to_blocks(num_bits, B::BLOCK_BITS),
+            "{num_blocks} blocks can't hold {num_bits} bits."
+        );
+    }
+
+    /// Number of (logical) bits.
+    pub fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_bits == 0
+    }
+
+    /// Panic if `index` is out of range. (Otherwise it could silently access
+    /// padding bits.)
+    fn check_index(&self, index: usize) {
+        assert!(
+            index < self.num_bits,
+            "Index {index} out of range for {} bits.",
+            self.num_bits
+        );
+    }
+
+    /// Pack `N` bits from `bits` into a new array-based instance. Panic if
+    /// `bits` has fewer than `N` items (like `SliceStorage` does).
+    fn pack_to_array(bits: impl Iterator<Item = bool>) -> Self {
+        let mut array = [B::ZERO; num_bits_to_blocks(N, B::BLOCK_BITS)];
+        let num_bits = pack_bits::<B, O>(&mut array, bits.take(N));
+        assert!(
+            num_bits == N,
+            "Expecting {N} items, but got {num_bits} only."
+        );
+        Self {
+            blocks: SliceStorageDefault::from_array(array),
+            num_bits: N,
+            bit_order: PhantomData,
+        }
+    }
+
+    with_heap! {
+        /// Pack (all) `bits` into a new `Vec`-based instance.
+        fn pack_to_vec(bits: impl Iterator<Item = bool>) -> Self {
+            Self::pack_to_heap(bits, SliceBackedChoice::Vec)
+        }
+    }
+
+    with_heap! {
+        /// Pack (all) `bits` into a new instance backed as per `as_choice`:
+        /// `Boxed`, `Rc`, `Arc`, otherwise `Vec`.
+        fn pack_to_heap(bits: impl Iterator<Item = bool>, as_choice: SliceBackedChoice) -> Self {
+            let mut num_bits = 0;
+            let vec = pack_bits_to_vec::<B, O>(bits.inspect(|_| num_bits += 1));
+            let blocks = match as_choice {
+                SliceBackedChoice::Boxed => SliceStorageDefault::from_boxed(vec.into()),
+                SliceBackedChoice::Rc => SliceStorageDefault::from_rc(vec.into()),
+                SliceBackedChoice::Arc => SliceStorageDefault::from_arc(vec.into()),
+                _ => SliceStorageDefault::from_vec(vec),
+            };
+            Self {
+                blocks,
+                num_bits,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    /// Pack a copy of `bools`. Array-based if it has `N` items, otherwise
+    /// `Vec`-based. Without heap it's array-based with up to `N` items, and
+    /// more items return `Error::LengthMismatch`.
+    fn try_pack_from_slice(bools: &[bool]) -> Result<Self, Error> {
+        if bools.len() == N {
+            return Ok(Self::pack_to_array(bools.iter().cloned()));
+        }
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+        return Ok(Self::pack_to_vec(bools.iter().cloned()));
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        {
+            if bools.len() > N {
+                return Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: bools.len(),
+                });
+            }
+            Ok(Self::pack_to_array_prefix(bools.iter().cloned()))
+        }
+    }
+
+    /// Like `pack_to_array`, but `bits` may have fewer than `N` items. (Then
+    /// the rest of the array is unused.) For copies without heap.
+    fn pack_to_array_prefix(bits: impl Iterator<Item = bool>) -> Self {
+        let mut array = [B::ZERO; num_bits_to_blocks(N, B::BLOCK_BITS)];
+        let num_bits = pack_bits::<B, O>(&mut array, bits.take(N));
+        Self {
+            blocks: SliceStorageDefault::from_array(array),
+            num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    /// Pack `bools` into a new instance backed as per `as_choice`: `Array`
+    /// (then `bools` must have `N` items), `Vec`, `Boxed`, `Rc` or `Arc`.
+    /// Other choices borrow, but packing creates new blocks, so they panic.
+    pub fn pack(bools: &[bool], as_choice: SliceBackedChoice) -> Self {
+        Self::try_pack(bools, as_choice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `pack`, but return `Error::LengthMismatch` or
+    /// `Error::UnsupportedChoice` rather than panic.
+    pub fn try_pack(bools: &[bool], as_choice: SliceBackedChoice) -> Result<Self, Error> {
+        match as_choice {
+            SliceBackedChoice::Array if bools.len() != N => Err(Error::LengthMismatch {
+                expected: N,
+                actual: bools.len(),
+            }),
+            SliceBackedChoice::Array => Ok(Self::pack_to_array(bools.iter().cloned())),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            SliceBackedChoice::Vec
+            | SliceBackedChoice::Boxed
+            | SliceBackedChoice::Rc
+            | SliceBackedChoice::Arc => Ok(Self::pack_to_heap(bools.iter().cloned(), as_choice)),
+            _ => Err(Error::UnsupportedChoice(as_choice)),
+        }
+    }
+
+    /// Like `pack`, but from a `BoolSlice` of any backing.
+    pub fn pack_bool_slice<const M: usize>(
+        bool_slice: &BoolSlice<'_, M>,
+        as_choice: SliceBackedChoice,
+    ) -> Self {
+        Self::pack(bool_slice.shared_slice(), as_choice)
+    }
+
+    /// Overwrite all bits from `bools`, in place (with no allocation). Panic
+    /// if `bools` doesn't have `len()` items.
+    pub fn pack_from(&mut self, bools: &[bool]) {
+        assert!(
+            bools.len() == self.num_bits,
+            "Expecting {} items, but got {}.",
+            self.num_bits,
+            bools.len()
+        );
+        self.modify_keeping_padding(|blocks| {
+            pack_bits::<B, O>(blocks, bools.iter().cloned());
+        });
+    }
+
+    /// Bits by value (rather than by reference).
+    fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        PackedBoolIter::<B, O>::new(self.blocks.shared_slice(), self.num_bits).cloned()
+    }
+
+    /// Unpack into a new `BoolSlice` backed as per `as_choice`: `Array` (then
+    /// this must have `N` bits), `Vec`, `Boxed`, `Rc` or `Arc`. Other choices
+    /// borrow, so they panic.
+    pub fn unpack<'b>(&self, as_choice: SliceBackedChoice) -> BoolSlice<'b, N> {
+        self.try_unpack(as_choice)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `unpack`, but return `Error::LengthMismatch` or
+    /// `Error::UnsupportedChoice` rather than panic.
+    pub fn try_unpack<'b>(&self, as_choice: SliceBackedChoice) -> Result<BoolSlice<'b, N>, Error> {
+        match as_choice {
+            SliceBackedChoice::Array if self.num_bits != N => Err(Error::LengthMismatch {
+                expected: N,
+                actual: self.num_bits,
+            }),
+            SliceBackedChoice::Array => Ok(BoolSlice::from_iter_to_array(self.bits())),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            SliceBackedChoice::Vec => Ok(BoolSlice::from_iter_to_vec(self.bits())),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            SliceBackedChoice::Boxed => Ok(BoolSlice::from_boxed(self.bits().collect())),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            SliceBackedChoice::Rc => Ok(BoolSlice::from_rc(self.bits().collect())),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            SliceBackedChoice::Arc => Ok(BoolSlice::from_arc(self.bits().collect())),
+            _ => Err(Error::UnsupportedChoice(as_choice)),
+        }
+    }
+
+    /// Unpack all bits into `bools`, in place (with no allocation). Panic if
+    /// `bools` doesn't have `len()` items.
+    pub fn unpack_into(&self, bools: &mut [bool]) {
+        assert!(
+            bools.len() == self.num_bits,
+            "Expecting {} items, but got {}.",
+            self.num_bits,
+            bools.len()
+        );
+        for (bool, bit) in bools.iter_mut().zip(self.bits()) {
+            *bool = bit;
+        }
+    }
+
+    /// Return (block_index, old_block, new_block)
+    fn dry_run_set(&self, index: usize, value: &bool) -> (usize, B, B) {
+        self.check_index(index);
+        let block_index = index / B::BLOCK_BITS;
+        let bit_subindex = index % B::BLOCK_BITS;
+        let old_block = self.blocks.get(block_index);
+
+        let new_block = if *value {
+            old_block | O::bit_mask::<B>(bit_subindex)
+        } else {
+            old_block & !O::bit_mask::<B>(bit_subindex)
+        };
+        (block_index, old_block, new_block)
+    }
+
+    /// Number of blocks that hold (logical) bits. The backing storage may have
+    /// more blocks than this - those are ignored.
+    fn num_blocks(&self) -> usize {
+        num_bits_to_blocks(self.num_bits, B::BLOCK_BITS)
+    }
+
+    /// Backing blocks that hold (logical) bits, laid out as per `O`. Any
+    /// padding bits in the last block are clear, unless this is a view of
+    /// external blocks.
+    pub fn as_blocks(&self) -> &[B] {
+        self.used_blocks()
+    }
+
+    /// Blocks that hold (logical) bits.
+    fn used_blocks(&self) -> &[B] {
+        &self.blocks.shared_slice()[..self.num_blocks()]
+    }
+
+    /// Block at `block_index` (which must be less than `num_blocks()`), with
+    /// any padding bits cleared.
+    fn masked_block(&self, block_index: usize) -> B {
+        let block = self.used_blocks()[block_index];
+        if block_index + 1 == self.num_blocks() {
+            block & O::prefix_mask::<B>(self.num_bits - block_index * B::BLOCK_BITS)
+        } else {
+            block
+        }
+    }
+
+    /// Like `used_blocks`, but mutable.
+    fn used_blocks_mut(&mut self) -> &mut [B] {
+        let num_blocks = self.num_blocks();
+        &mut self.blocks.mutable_slice()[..num_blocks]
+    }
+
+    /// Run `f` on `used_blocks_mut()`, but keep any padding bits in the last
+    /// block unchanged. (If this is a view of external blocks, those bits are
+    /// not ours to change.)
+    fn modify_keeping_padding(&mut self, f: impl FnOnce(&mut [B])) {
+        let used_bits_in_last = self.num_bits % B::BLOCK_BITS;
+        let blocks = self.used_blocks_mut();
+        if used_bits_in_last == 0 {
+            f(blocks);
+        } else {
+            let last = blocks.len() - 1;
+            let used_mask = O::prefix_mask::<B>(used_bits_in_last);
+            let padding = blocks[last] & !used_mask;
+            f(&mut *blocks);
+            blocks[last] = (blocks[last] & used_mask) | padding;
+        }
+    }
+
+    /// Clear any padding bits in the last block.
+    fn clear_padding(&mut self) {
+        let used_bits_in_last = self.num_bits % B::BLOCK_BITS;
+        if used_bits_in_last != 0 {
+            let last = self.num_blocks() - 1;
+            self.blocks.mutable_slice()[last] &= O::prefix_mask::<B>(used_bits_in_last);
+        }
+    }
+
+    /// Copy to a new instance that owns its blocks. Array-based if `self` is
+    /// array-based, otherwise `Vec`-based. Without heap it's array-based, so
+    /// then it panics if `self` has more than `N` bits. Padding bits of the
+    /// copy are clear.
+    fn to_owned_copy(&self) -> Self {
+        let blocks = match &self.blocks {
+            SliceStorageDefault::Array(array) => SliceStorageDefault::Array(*array),
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            _ => SliceStorageDefault::Vec(self.used_blocks().to_vec()),
+            #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+            _ => {
+                assert!(
+                    self.num_bits <= N,
+                    "Copying more than {N} bits requires heap."
+                );
+                let mut array = [B::ZERO; num_bits_to_blocks(N, B::BLOCK_BITS)];
+                let used_blocks = self.used_blocks();
+                array[..used_blocks.len()].copy_from_slice(used_blocks);
+                SliceStorageDefault::Array(array)
+            }
+        };
+        let mut copy = Self {
+            blocks,
+            num_bits: self.num_bits,
+            bit_order: PhantomData,
+        };
+        copy.clear_padding();
+        copy
+    }
+
+    /// Combine blocks of `self` with blocks of `other` in place. Both must have
+    /// the same number of bits.
+    fn combine_assign<'o, const M: usize>(
+        &mut self,
+        other: &PackedBoolStorage<'o, B, M, O>,
+        op: BitwiseOp,
+    ) where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        assert_eq!(
+            self.num_bits, other.num_bits,
+            "Expecting the same number of bits."
+        );
+        self.modify_keeping_padding(|blocks| {
+            B::simd_combine_assign(blocks, other.used_blocks(), op);
+        });
+    }
+
+    // Bulk bitwise operations. In-place ones (`..._assign`) require `self` not
+    // to be Shared-based. Allocating ones return a copy (see `to_owned_copy`).
+    // Each `other` must have the same number of bits as `self`.
+
+    /// Intersection.
+    pub fn and_assign<'o, const M: usize>(&mut self, other: &PackedBoolStorage<'o, B, M, O>)
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        self.combine_assign(other, BitwiseOp::And);
+    }
+    /// Union.
+    pub fn or_assign<'o, const M: usize>(&mut self, other: &PackedBoolStorage<'o, B, M, O>)
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        self.combine_assign(other, BitwiseOp::Or);
+    }
+    /// Symmetric difference.
+    pub fn xor_assign<'o, const M: usize>(&mut self, other: &PackedBoolStorage<'o, B, M, O>)
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        self.combine_assign(other, BitwiseOp::Xor);
+    }
+    /// Difference: Clear any bits that are set in `other`.
+    pub fn and_not_assign<'o, const M: usize>(&mut self, other: &PackedBoolStorage<'o, B, M, O>)
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        self.combine_assign(other, BitwiseOp::AndNot);
+    }
+    /// Complement.
+    pub fn not_assign(&mut self) {
+        self.modify_keeping_padding(|blocks| B::simd_not_assign(blocks));
+    }
+
+    /// Set all bits to `value`. (Requires `self` not to be Shared-based.)
+    pub fn fill(&mut self, value: bool) {
+        let block = if value { B::ONES } else { B::ZERO };
+        self.modify_keeping_padding(|blocks| B::simd_fill(blocks, block));
+    }
+
+    /// Combine bits with the respective bits of `other`, which must have the
+    /// same number of bits. (Requires `self` not to be Shared-based.)
+    pub fn bitwise_assign<'o, const M: usize>(
+        &mut self,
+        other: &PackedBoolStorage<'o, B, M, O>,
+        op: BitwiseOp,
+    ) where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        self.combine_assign(other, op);
+    }
+
+    /// Index of the first bit equal to `value`. It skips whole blocks of the
+    /// other value at once.
+    pub fn find_first(&self, value: bool) -> Option<usize> {
+        let skipped = if value { B::ZERO } else { B::ONES };
+        let full_blocks = self.num_blocks().saturating_sub(1);
+        let block_index = B::simd_find_first_other(&self.used_blocks()[..full_blocks], skipped)
+            .unwrap_or(full_blocks);
+        let from = block_index * B::BLOCK_BITS;
+        if value {
+            self.next_one(from)
+        } else {
+            self.next_zero(from)
+        }
+    }
+
+    /// Number of bits equal to `value`.
+    pub fn count_matching(&self, value: bool) -> usize {
+        if value {
+            self.count_ones()
+        } else {
+            self.count_zeros()
+        }
+    }
+
+    /// Whether `other` has the same (logical) bits. Any padding bits don't
+    /// matter.
+    pub fn bits_eq<'o, const M: usize>(&self, other: &PackedBoolStorage<'o, B, M, O>) -> bool
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        if self.num_bits != other.num_bits {
+            return false;
+        }
+        match self.num_blocks() {
+            0 => true,
+            num_blocks => {
+                let full_blocks = num_blocks - 1;
+                B::simd_eq(
+                    &self.used_blocks()[..full_blocks],
+                    &other.used_blocks()[..full_blocks],
+                ) && self.masked_block(full_blocks) == other.masked_block(full_blocks)
+            }
+        }
+    }
+
+    pub fn and<'o, const M: usize>(&self, other: &PackedBoolStorage<'o, B, M, O>) -> Self
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        let mut result = self.to_owned_copy();
+        result.and_assign(other);
+        result
+    }
+    pub fn or<'o, const M: usize>(&self, other: &PackedBoolStorage<'o, B, M, O>) -> Self
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        let mut result = self.to_owned_copy();
+        result.or_assign(other);
+        result
+    }
+    pub fn xor<'o, const M: usize>(&self, other: &PackedBoolStorage<'o, B, M, O>) -> Self
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        let mut result = self.to_owned_copy();
+        result.xor_assign(other);
+        result
+    }
+    pub fn and_not<'o, const M: usize>(&self, other: &PackedBoolStorage<'o, B, M, O>) -> Self
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        let mut result = self.to_owned_copy();
+        result.and_not_assign(other);
+        result
+    }
+    /// Not called `not`, so it doesn't shadow `core::ops::Not::not`.
+    pub fn complement(&self) -> Self {
+        let mut result = self.to_owned_copy();
+        result.not_assign();
+        result
+    }
+}
+
+impl<'a, const N: usize, O: BitOrder> PackedBoolStorage<'a, u8, N, O>
+where
+    [(); num_bits_to_blocks(N, u8::BLOCK_BITS)]:,
+{
+    /// View `bytes` (for example, from a network packet or a memory-mapped
+    /// file) as `num_bits` packed bits, without copying. See
+    /// `from_shared_blocks`.
+    pub fn from_shared_bytes(bytes: &'a [u8], num_bits: usize) -> Self {
+        Self::from_shared_blocks(bytes, num_bits)
+    }
+
+    /// Like `from_shared_bytes`, but modifiable in place.
+    pub fn from_mutable_bytes(bytes: &'a mut [u8], num_bits: usize) -> Self {
+        Self::from_mutable_blocks(bytes, num_bits)
+    }
+}
+
+/// Generate operator traits for a bulk bitwise operation: `$op_assign_trait`
+/// (in place), `$op_trait` for `Self` (in place, returning `self`) and
+/// `$op_trait` for `&Self` (allocating).
+macro_rules! packed_bool_op_impl {
+    ($op_trait:ident, $op_fn:ident, $op_assign_trait:ident, $op_assign_fn:ident, $inherent_assign:ident, $inherent:ident) => {
+        impl<'a, 'o, B: BitBlock, const N: usize, const M: usize, O: BitOrder>
+            core::ops::$op_assign_trait<&PackedBoolStorage<'o, B, M, O>>
+            for PackedBoolStorage<'a, B, N, O>
+        where
+            [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+            [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+        {
+            fn $op_assign_fn(&mut self, other: &PackedBoolStorage<'o, B, M, O>) {
+                self.$inherent_assign(other);
+            }
+        }
+
+        impl<'a, 'o, B: BitBlock, const N: usize, const M: usize, O: BitOrder>
+            $op_trait<&PackedBoolStorage<'o, B, M, O>> for PackedBoolStorage<'a, B, N, O>
+        where
+            [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+            [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+        {
+            type Output = Self;
+            fn $op_fn(mut self, other: &PackedBoolStorage<'o, B, M, O>) -> Self {
+                self.$inherent_assign(other);
+                self
+            }
+        }
+
+        impl<'a, 'o, B: BitBlock, const N: usize, const M: usize, O: BitOrder>
+            $op_trait<&PackedBoolStorage<'o, B, M, O>> for &PackedBoolStorage<'a, B, N, O>
+        where
+            [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+            [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+        {
+            type Output = PackedBoolStorage<'a, B, N, O>;
+            fn $op_fn(self, other: &PackedBoolStorage<'o, B, M, O>) -> Self::Output {
+                self.$inherent(other)
+            }
+        }
+    };
+}
+
+packed_bool_op_impl!(BitAnd, bitand, BitAndAssign, bitand_assign, and_assign, and);
+packed_bool_op_impl!(BitOr, bitor, BitOrAssign, bitor_assign, or_assign, or);
+packed_bool_op_impl!(BitXor, bitxor, BitXorAssign, bitxor_assign, xor_assign, xor);
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> Not for PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    type Output = Self;
+    /// In place.
+    fn not(mut self) -> Self {
+        self.not_assign();
+        self
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> Not for &PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    type Output = PackedBoolStorage<'a, B, N, O>;
+    /// Allocating.
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> SliceRead<bool> for PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    type ITER<'s> = PackedBoolIter<'s, B, O>
+    where Self: 's;
+
+    fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        let block = self.blocks.get(index / B::BLOCK_BITS);
+        get_bit::<B, O>(block, index % B::BLOCK_BITS)
+    }
+    fn iter<'s>(&'s self) -> Self::ITER<'s> {
+        Self::ITER::new(self.blocks.shared_slice(), self.num_bits)
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> SliceWrite<bool> for PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    fn set(&mut self, index: usize, value: &bool) {
+        let (block_index, _, new_block) = self.dry_run_set(index, value);
+        self.blocks.set(block_index, &new_block);
+    }
+    fn try_set(&mut self, index: usize, value: &bool) -> Result<(), Error> {
+        self.try_check_and_set(index, value).map(|_| ())
+    }
+    fn check_and_set(&mut self, index: usize, value: &bool) -> bool {
+        let (block_index, old_block, new_block) = self.dry_run_set(index, value);
+        self.blocks.set(block_index, &new_block);
+        old_block != new_block
+    }
+    fn try_check_and_set(&mut self, index: usize, value: &bool) -> Result<bool, Error> {
+        error::check_index(index, self.num_bits)?;
+        let (block_index, old_block, new_block) = self.dry_run_set(index, value);
+        self.blocks.try_set(block_index, &new_block)?;
+        Ok(old_block != new_block)
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> SliceIterMut<bool>
+    for PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    type ITER_MUT<'s> = PackedBoolIterMut<'s, B, O>
+    where Self: 's;
+
+    fn try_iter_mut<'s>(&'s mut self) -> Result<Self::ITER_MUT<'s>, Error> {
+        let num_bits = self.num_bits;
+        let blocks = self.blocks.try_mutable_slice()?;
+        Ok(PackedBoolIterMut::new(
+            Cell::from_mut(blocks).as_slice_of_cells(),
+            num_bits,
+        ))
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> SliceDefault<'a, bool, N>
+    for PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+    [(); num_bits_to_blocks(0, B::BLOCK_BITS)]:,
+{
+    with_heap! {
+        type NARR = PackedBoolStorage<'a, B, 0, O>;
+    }
+
+    // Ownership transfer constructors. They can't transfer ownership of
+    // unpacked bools, so `from_shared` packs a copy instead. Writes to a copy
+    // wouldn't reach `slice` or `vector`, so `from_mutable` and `from_vec_ref`
+    // panic (and their `try_*` counterparts return `Error::NotSliceBacked`).
+    // Use `from_mutable_blocks` to modify packed bits in place.
+    fn from_shared(slice: &'a [bool]) -> Self {
+        Self::try_from_shared(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_shared(slice: &'a [bool]) -> Result<Self, Error> {
+        Self::try_pack_from_slice(slice)
+    }
+    fn from_mutable(slice: &'a mut [bool]) -> Self {
+        Self::try_from_mutable(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_mutable(_slice: &'a mut [bool]) -> Result<Self, Error> {
+        Err(Error::NotSliceBacked)
+    }
+    fn from_array(array: [bool; N]) -> Self {
+        Self::pack_to_array(array.into_iter())
+    }
+
+    with_heap! {
+        fn from_vec(vector: Vec<bool>) -> Self {
+            Self::pack_to_vec(vector.into_iter())
+        }
+    }
+    with_heap! {
+        fn from_vec_ref(vector: &'a mut Vec<bool>) -> Self {
+            Self::try_from_vec_ref(vector).unwrap_or_else(|error| panic!("{error}"))
+        }
+    }
+    with_heap! {
+        fn try_from_vec_ref(_vector: &'a mut Vec<bool>) -> Result<Self, Error> {
+            Err(Error::NotSliceBacked)
+        }
+    }
+
+    fn from_value_to_array(value_ref: &bool) -> Self {
+        Self::pack_to_array(core::iter::repeat(*value_ref))
+    }
+
+    with_heap! {
+        fn from_value_to_vec(value: &bool, size: usize) -> Self {
+            Self {
+                blocks: SliceStorageDefault::from_vec(pack_bits_to_vec::<B, O>(
+                    core::iter::repeat_n(*value, size),
+                )),
+                num_bits: size,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    fn from_iter_to_array(iter: impl Iterator<Item = bool>) -> Self {
+        Self::pack_to_array(iter)
+    }
+    with_heap! {
+        fn from_iter_to_vec(iter: impl Iterator<Item = bool>) -> Self {
+            Self::pack_to_vec(iter)
+        }
+    }
+
+    fn from_fn_to_array(f: impl FnMut() -> bool) -> Self {
+        Self::pack_to_array(core::iter::repeat_with(f))
+    }
+    with_heap! {
+        fn from_fn_to_vec(f: impl FnMut() -> bool, size: usize) -> Self {
+            Self {
+                blocks: SliceStorageDefault::from_vec(pack_bits_to_vec::<B, O>(
+                    core::iter::repeat_with(f).take(size),
+                )),
+                num_bits: size,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    fn from_default(size: usize, storage_type: SliceBackedChoice) -> Self {
+        Self::try_from_default(size, storage_type).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_default(size: usize, storage_type: SliceBackedChoice) -> Result<Self, Error> {
+        let num_bits = if storage_type.is_array() { N } else { size };
+        Ok(Self {
+            blocks: SliceStorageDefault::try_from_default(
+                num_bits_to_blocks(size, B::BLOCK_BITS),
+                storage_type,
+            )?,
+            num_bits,
+            bit_order: PhantomData,
+        })
+    }
+    fn from_default_to_array() -> Self {
+        Self {
+            blocks: SliceStorageDefault::from_default_to_array(),
+            num_bits: N,
+            bit_order: PhantomData,
+        }
+    }
+    with_heap! {
+        fn from_default_to_vec(size: usize) -> Self {
+            Self {
+                blocks: SliceStorageDefault::from_default_to_vec(num_bits_to_blocks(
+                    size,
+                    B::BLOCK_BITS,
+                )),
+                num_bits: size,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    fn to_array_based(&self) -> Self {
+        Self::pack_to_array(self.bits())
+    }
+
+    with_heap! {
+        fn to_vec_based(self) -> Self {
+            Self {
+                blocks: self.blocks.to_vec_based(),
+                num_bits: self.num_bits,
+                bit_order: PhantomData,
+            }
+        }
+    }
+    with_heap! {
+        fn to_non_array_vec_based(&self) -> Self::NARR {
+            // Not delegating to `SliceStorageDefault::to_non_array_vec_based()`,
+            // because the compiler can't unify its `0` with NARR's
+            // `num_bits_to_blocks(0, B::BLOCK_BITS)` for a generic `B`.
+            Self::NARR {
+                blocks: SliceStorageDefault::from_vec(self.blocks.shared_slice().to_vec()),
+                num_bits: self.num_bits,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    with_heap! {
+        fn try_from_non_array_vec_based(from: &Self::NARR,
+            as_choice: &SliceBackedChoice
+        ) -> Result<Self, Error> {
+            let from_blocks = from.blocks.shared_slice();
+            match as_choice {
+                SliceBackedChoice::Array if from.num_bits != N => Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: from.num_bits,
+                }),
+                SliceBackedChoice::Array => Ok(Self {
+                    blocks: SliceStorageDefault::from_array(core::array::from_fn(|i| {
+                        from_blocks[i]
+                    })),
+                    num_bits: N,
+                    bit_order: PhantomData,
+                }),
+                SliceBackedChoice::Inline
+                | SliceBackedChoice::Vec
+                | SliceBackedChoice::Boxed
+                | SliceBackedChoice::Rc
+                | SliceBackedChoice::Arc => Ok(Self {
+                    blocks: SliceStorageDefault::try_from_iter_to(
+                        from_blocks.iter().copied(),
+                        from_blocks.len(),
+                        *as_choice,
+                    )?,
+                    num_bits: from.num_bits,
+                    bit_order: PhantomData,
+                }),
+                SliceBackedChoice::Shared | SliceBackedChoice::Mutable | SliceBackedChoice::VecRef => {
+                    Err(Error::UnsupportedChoice(*as_choice))
+                }
+            }
+        }
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> NewEmptyLike for PackedBoolStorage<'a, B, N, O>
+where
+    Self: 'a,
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    fn new_empty_like(&self) -> Self {
+        let blocks = self.blocks.new_empty_like();
+        // Inline-based and Vec-based instances become empty. Others keep
+        // their size.
+        let num_bits = match_cfg! {&blocks,
+            SliceStorageDefault::Inline(_) => 0,
+            ~[heap~]
+            SliceStorageDefault::Vec(_) => 0,
+            _ => self.num_bits
+        };
+        Self {
+            blocks,
+            num_bits,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+/// Iterate over (logical) bits only - not over any padding bits.
+#[derive(Debug, Clone)]
+pub struct PackedBoolIter<'a, B: BitBlock, O: BitOrder = Lsb0> {
+    blocks: &'a [B],
+    /// Index of the next bit to return from the front.
+    front: usize,
+    /// Index right after the next bit to return from the back.
+    back: usize,
+    bit_order: PhantomData<O>,
+}
+
+impl<'a, B: BitBlock, O: BitOrder> PackedBoolIter<'a, B, O> {
+    fn new(blocks: &'a [B], num_bits: usize) -> Self {
+        debug_assert!(num_bits <= blocks.len() * B::BLOCK_BITS);
+        Self {
+            blocks,
+            front: 0,
+            back: num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    fn bit_ref(&self, index: usize) -> &'a bool {
+        if get_bit::<B, O>(self.blocks[index / B::BLOCK_BITS], index % B::BLOCK_BITS) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> Iterator for PackedBoolIter<'a, B, O> {
+    type Item = &'a bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a bool> {
+        if self.front < self.back {
+            let result = self.bit_ref(self.front);
+            self.front += 1;
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a bool> {
+        self.front = self.back.min(self.front.saturating_add(n));
+        self.next()
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> DoubleEndedIterator for PackedBoolIter<'a, B, O> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a bool> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.bit_ref(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> ExactSizeIterator for PackedBoolIter<'a, B, O> {}
+
+impl<'a, B: BitBlock, O: BitOrder> core::iter::FusedIterator for PackedBoolIter<'a, B, O> {}
+
+/// Like `PackedBoolIter`, but yielding `PackedBoolMut` proxies.
+#[derive(Debug)]
+pub struct PackedBoolIterMut<'a, B: BitBlock, O: BitOrder = Lsb0> {
+    /// Cells (rather than `&mut [B]`), because proxies of bits in the same
+    /// block may be alive at the same time.
+    blocks: &'a [Cell<B>],
+    front: usize,
+    back: usize,
+    bit_order: PhantomData<O>,
+}
+
+impl<'a, B: BitBlock, O: BitOrder> PackedBoolIterMut<'a, B, O> {
+    fn new(blocks: &'a [Cell<B>], num_bits: usize) -> Self {
+        debug_assert!(num_bits <= blocks.len() * B::BLOCK_BITS);
+        Self {
+            blocks,
+            front: 0,
+            back: num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    fn bit_mut(&self, index: usize) -> PackedBoolMut<'a, B, O> {
+        let block = &self.blocks[index / B::BLOCK_BITS];
+        let bit_subindex = index % B::BLOCK_BITS;
+        PackedBoolMut {
+            block,
+            bit_subindex,
+            value: get_bit::<B, O>(block.get(), bit_subindex),
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> Iterator for PackedBoolIterMut<'a, B, O> {
+    type Item = PackedBoolMut<'a, B, O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let result = self.bit_mut(self.front);
+            self.front += 1;
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.back.min(self.front.saturating_add(n));
+        self.next()
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> DoubleEndedIterator for PackedBoolIterMut<'a, B, O> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.bit_mut(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> ExactSizeIterator for PackedBoolIterMut<'a, B, O> {}
+
+impl<'a, B: BitBlock, O: BitOrder> core::iter::FusedIterator for PackedBoolIterMut<'a, B, O> {}
+
+/// Proxy for one packed bit. It dereferences to a `bool`, and it writes that
+/// `bool` back (to its bit only) when dropped.
+#[derive(Debug)]
+pub struct PackedBoolMut<'a, B: BitBlock, O: BitOrder = Lsb0> {
+    block: &'a Cell<B>,
+    bit_subindex: usize,
+    value: bool,
+    bit_order: PhantomData<O>,
+}
+
+impl<'a, B: BitBlock, O: BitOrder> Deref for PackedBoolMut<'a, B, O> {
+    type Target = bool;
+
+    fn deref(&self) -> &bool {
+        &self.value
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> DerefMut for PackedBoolMut<'a, B, O> {
+    fn deref_mut(&mut self) -> &mut bool {
+        &mut self.value
+    }
+}
+
+impl<'a, B: BitBlock, O: BitOrder> Drop for PackedBoolMut<'a, B, O> {
+    fn drop(&mut self) {
+        // Read the block now (rather than when this proxy was created), so
+        // that other proxies' writes to the same block are kept.
+        let mask = O::bit_mask::<B>(self.bit_subindex);
+        let block = self.block.get();
+        self.block.set(if self.value {
+            block | mask
+        } else {
+            block & !mask
+        });
+    }
+}
@@ -0,0 +1,311 @@
+use super::{get_bit, num_bits_to_blocks, BitBlock, BitOrder, Lsb0, PackedBoolStorage};
+use crate::error::Error;
+use crate::slices::SliceStorageDefault;
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::vec::Vec;
+
+use crate::with_heap;
+
+/// A `BitBlock` that has an atomic counterpart (on the target platform).
+pub trait AtomicBlock: BitBlock {
+    /// `core::sync::atomic::AtomicU8` etc.
+    type Atomic: Debug + Send + Sync;
+
+    fn new_atomic(block: Self) -> Self::Atomic;
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+    /// Return the previous block.
+    fn fetch_or(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self;
+    /// Return the previous block.
+    fn fetch_and(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self;
+    /// Return the previous block.
+    fn fetch_xor(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self;
+}
+
+macro_rules! atomic_block_impl {
+    ($($block:ty => $atomic:ident, $has_atomic:literal);+) => {
+        $(
+            #[cfg(target_has_atomic = $has_atomic)]
+            impl AtomicBlock for $block {
+                type Atomic = core::sync::atomic::$atomic;
+
+                #[inline]
+                fn new_atomic(block: Self) -> Self::Atomic {
+                    Self::Atomic::new(block)
+                }
+                #[inline]
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+                #[inline]
+                fn fetch_or(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self {
+                    atomic.fetch_or(block, order)
+                }
+                #[inline]
+                fn fetch_and(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self {
+                    atomic.fetch_and(block, order)
+                }
+                #[inline]
+                fn fetch_xor(atomic: &Self::Atomic, block: Self, order: Ordering) -> Self {
+                    atomic.fetch_xor(block, order)
+                }
+            }
+        )+
+    };
+}
+atomic_block_impl!(
+    u8 => AtomicU8, "8";
+    u16 => AtomicU16, "16";
+    u32 => AtomicU32, "32";
+    u64 => AtomicU64, "64";
+    usize => AtomicUsize, "ptr"
+);
+
+/// Backing atomic blocks. Atomics are neither `Copy` nor `Clone`, hence not
+/// `crate::slices::SliceStorage*`. And since all modifications go through
+/// `&self`, a shared reference is enough - there is no `Mutable` variant.
+#[derive(Debug)]
+enum AtomicBlocks<'a, A, const NUM_BLOCKS: usize> {
+    Shared(&'a [A]),
+    Array([A; NUM_BLOCKS]),
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+    Vec(Vec<A>),
+}
+
+/// Concurrent counterpart of `PackedBoolStorage`, backed by atomic blocks
+/// (`AtomicU8`, `AtomicUsize`...). All modifications are lock-free, through
+/// `&self`, so an instance can be shared between threads. Loads use
+/// `Ordering::Acquire`, and modifications use `Ordering::AcqRel`. See
+/// `crate::slices::SliceStorage` for const generic parameter `N`.
+#[derive(Debug)]
+pub struct AtomicPackedBoolStorage<'a, B: AtomicBlock, const N: usize, O: BitOrder = Lsb0>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    blocks: AtomicBlocks<'a, B::Atomic, { num_bits_to_blocks(N, B::BLOCK_BITS) }>,
+    /// Logical number of bits. Equal to `N` if array-based. Padding bits are
+    /// never modified.
+    num_bits: usize,
+    bit_order: PhantomData<O>,
+}
+
+impl<'a, B: AtomicBlock, const N: usize, O: BitOrder> AtomicPackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    /// New array-based instance of `N` clear bits.
+    pub fn new_array() -> Self {
+        Self {
+            blocks: AtomicBlocks::Array(core::array::from_fn(|_| B::new_atomic(B::ZERO))),
+            num_bits: N,
+            bit_order: PhantomData,
+        }
+    }
+
+    with_heap! {
+        /// New `Vec`-based instance of `num_bits` clear bits.
+        pub fn new_vec(num_bits: usize) -> Self {
+            Self {
+                blocks: AtomicBlocks::Vec(
+                    (0..num_bits_to_blocks(num_bits, B::BLOCK_BITS))
+                        .map(|_| B::new_atomic(B::ZERO))
+                        .collect(),
+                ),
+                num_bits,
+                bit_order: PhantomData,
+            }
+        }
+    }
+
+    /// View `atomics` as `num_bits` packed bits (laid out like in
+    /// `PackedBoolStorage::from_shared_blocks`). Panic if `atomics` are too
+    /// short.
+    pub fn from_shared_atomics(atomics: &'a [B::Atomic], num_bits: usize) -> Self {
+        assert!(
+            atomics.len() >= num_bits_to_blocks(num_bits, B::BLOCK_BITS),
+            "{} blocks can't hold {num_bits} bits.",
+            atomics.len()
+        );
+        Self {
+            blocks: AtomicBlocks::Shared(atomics),
+            num_bits,
+            bit_order: PhantomData,
+        }
+    }
+
+    /// Copy bits of `packed`. Array-based if `packed` has `N` bits, otherwise
+    /// `Vec`-based. Without heap, panic unless `packed` has `N` bits.
+    pub fn from_packed<const M: usize>(packed: &PackedBoolStorage<'_, B, M, O>) -> Self
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        Self::try_from_packed(packed).unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `from_packed`, but without heap return `Error::LengthMismatch`
+    /// (rather than panic) unless `packed` has `N` bits.
+    pub fn try_from_packed<const M: usize>(
+        packed: &PackedBoolStorage<'_, B, M, O>,
+    ) -> Result<Self, Error>
+    where
+        [(); num_bits_to_blocks(M, B::BLOCK_BITS)]:,
+    {
+        let from_blocks = packed.as_blocks();
+        let blocks = if packed.len() == N {
+            AtomicBlocks::Array(core::array::from_fn(|i| {
+                B::new_atomic(from_blocks.get(i).copied().unwrap_or(B::ZERO))
+            }))
+        } else {
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            {
+                AtomicBlocks::Vec(
+                    from_blocks
+                        .iter()
+                        .map(|block| B::new_atomic(*block))
+                        .collect(),
+                )
+            }
+            #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+            return Err(Error::LengthMismatch {
+                expected: N,
+                actual: packed.len(),
+            });
+        };
+        Ok(Self {
+            blocks,
+            num_bits: packed.len(),
+            bit_order: PhantomData,
+        })
+    }
+
+    /// Copy current bits to a new `PackedBoolStorage`. Array-based if `self`
+    /// has `N` bits, otherwise `Vec`-based. Without heap, panic unless `self`
+    /// has `N` bits. Each block is loaded atomically, but not the whole
+    /// snapshot.
+    pub fn to_packed<'p>(&self) -> PackedBoolStorage<'p, B, N, O> {
+        self.try_to_packed()
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `to_packed`, but without heap return `Error::LengthMismatch`
+    /// (rather than panic) unless `self` has `N` bits.
+    pub fn try_to_packed<'p>(&self) -> Result<PackedBoolStorage<'p, B, N, O>, Error> {
+        let atomics = self.atomics();
+        let blocks = if self.num_bits == N {
+            SliceStorageDefault::Array(core::array::from_fn(|i| {
+                B::load(&atomics[i], Ordering::Acquire)
+            }))
+        } else {
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            {
+                SliceStorageDefault::Vec(
+                    atomics[..num_bits_to_blocks(self.num_bits, B::BLOCK_BITS)]
+                        .iter()
+                        .map(|atomic| B::load(atomic, Ordering::Acquire))
+                        .collect(),
+                )
+            }
+            #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+            return Err(Error::LengthMismatch {
+                expected: N,
+                actual: self.num_bits,
+            });
+        };
+        let mut packed = PackedBoolStorage {
+            blocks,
+            num_bits: self.num_bits,
+            bit_order: PhantomData,
+        };
+        packed.clear_padding();
+        Ok(packed)
+    }
+
+    /// Number of (logical) bits.
+    pub fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_bits == 0
+    }
+
+    fn atomics(&self) -> &[B::Atomic] {
+        match &self.blocks {
+            AtomicBlocks::Shared(slice) => slice,
+            AtomicBlocks::Array(array) => array,
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            AtomicBlocks::Vec(vec) => vec,
+        }
+    }
+
+    /// Return the atomic block that holds bit at `index`, and the bit's mask
+    /// within that block. Panic if `index` is out of range.
+    fn atomic_and_mask(&self, index: usize) -> (&B::Atomic, B) {
+        assert!(
+            index < self.num_bits,
+            "Index {index} out of range for {} bits.",
+            self.num_bits
+        );
+        (
+            &self.atomics()[index / B::BLOCK_BITS],
+            O::bit_mask::<B>(index % B::BLOCK_BITS),
+        )
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        let (atomic, _) = self.atomic_and_mask(index);
+        get_bit::<B, O>(B::load(atomic, Ordering::Acquire), index % B::BLOCK_BITS)
+    }
+
+    /// Set bit at `index` to `true`.
+    pub fn set(&self, index: usize) {
+        self.test_and_set(index);
+    }
+
+    /// Set bit at `index` to `false`.
+    pub fn clear(&self, index: usize) {
+        self.test_and_clear(index);
+    }
+
+    /// Set bit at `index` to `true`. Return true if it was `false` (like
+    /// `SliceDefault::check_and_set` returns true if it modifies the bit). Of
+    /// concurrent calls for the same bit, exactly one returns true.
+    pub fn test_and_set(&self, index: usize) -> bool {
+        let (atomic, mask) = self.atomic_and_mask(index);
+        B::fetch_or(atomic, mask, Ordering::AcqRel) & mask == B::ZERO
+    }
+
+    /// Set bit at `index` to `false`. Return true if it was `true`.
+    pub fn test_and_clear(&self, index: usize) -> bool {
+        let (atomic, mask) = self.atomic_and_mask(index);
+        B::fetch_and(atomic, !mask, Ordering::AcqRel) & mask != B::ZERO
+    }
+
+    /// Toggle bit at `index`. Return its previous value.
+    pub fn fetch_toggle(&self, index: usize) -> bool {
+        let (atomic, mask) = self.atomic_and_mask(index);
+        B::fetch_xor(atomic, mask, Ordering::AcqRel) & mask != B::ZERO
+    }
+
+    /// Number of set (`true`) bits. Each block is loaded atomically, but not
+    /// all blocks at once.
+    pub fn count_ones(&self) -> usize {
+        let num_blocks = num_bits_to_blocks(self.num_bits, B::BLOCK_BITS);
+        let used_bits_in_last = self.num_bits % B::BLOCK_BITS;
+        self.atomics()[..num_blocks]
+            .iter()
+            .enumerate()
+            .map(|(block_index, atomic)| {
+                let block = B::load(atomic, Ordering::Acquire);
+                let block = if block_index + 1 == num_blocks && used_bits_in_last != 0 {
+                    block & O::prefix_mask::<B>(used_bits_in_last)
+                } else {
+                    block
+                };
+                block.count_ones() as usize
+            })
+            .sum()
+    }
+}
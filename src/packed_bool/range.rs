@@ -0,0 +1,97 @@
+use super::{num_bits_to_blocks, BitBlock, BitOrder, PackedBoolStorage};
+use core::ops::Range;
+
+/// Iterate over `(block_index, mask)` of all blocks that overlap `range`, where
+/// `mask` selects bits of that block that are within `range`. Blocks fully
+/// within `range` have mask `B::ONES`, so that callers handle them with a
+/// single store.
+fn block_masks<B: BitBlock, O: BitOrder>(range: Range<usize>) -> impl Iterator<Item = (usize, B)> {
+    let first_block = range.start / B::BLOCK_BITS;
+    let end_block = if range.start < range.end {
+        num_bits_to_blocks(range.end, B::BLOCK_BITS)
+    } else {
+        first_block
+    };
+    (first_block..end_block).map(move |block_index| {
+        let block_start = block_index * B::BLOCK_BITS;
+        let low = range.start.max(block_start) - block_start;
+        let high = range.end.min(block_start + B::BLOCK_BITS) - block_start;
+        (
+            block_index,
+            O::prefix_mask::<B>(high) & !O::prefix_mask::<B>(low),
+        )
+    })
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    /// Panic if `range` is not a valid range within bits (like slice indexing
+    /// does).
+    fn check_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.num_bits,
+            "Range {}..{} out of range for {} bits.",
+            range.start,
+            range.end,
+            self.num_bits
+        );
+    }
+
+    /// Apply `f(block, mask)` to all blocks that overlap `range`.
+    fn modify_range(&mut self, range: Range<usize>, f: impl Fn(B, B) -> B) {
+        self.check_range(&range);
+        let blocks = self.used_blocks_mut();
+        for (block_index, mask) in block_masks::<B, O>(range) {
+            blocks[block_index] = f(blocks[block_index], mask);
+        }
+    }
+
+    /// Set all bits in `range`.
+    pub fn set_range(&mut self, range: Range<usize>) {
+        self.modify_range(range, |block, mask| block | mask);
+    }
+
+    /// Clear all bits in `range`.
+    pub fn clear_range(&mut self, range: Range<usize>) {
+        self.modify_range(range, |block, mask| block & !mask);
+    }
+
+    /// Set or clear all bits in `range`, depending on `value`.
+    pub fn fill_range(&mut self, range: Range<usize>, value: bool) {
+        if value {
+            self.set_range(range);
+        } else {
+            self.clear_range(range);
+        }
+    }
+
+    /// Negate all bits in `range`.
+    pub fn toggle_range(&mut self, range: Range<usize>) {
+        self.modify_range(range, |block, mask| block ^ mask);
+    }
+
+    /// Number of set bits in `range`.
+    pub fn count_ones_in(&self, range: Range<usize>) -> usize {
+        self.check_range(&range);
+        let blocks = self.used_blocks();
+        block_masks::<B, O>(range)
+            .map(|(block_index, mask)| (blocks[block_index] & mask).count_ones() as usize)
+            .sum()
+    }
+
+    /// Whether any bit in `range` is set. `false` for an empty `range`.
+    pub fn any_in(&self, range: Range<usize>) -> bool {
+        self.check_range(&range);
+        let blocks = self.used_blocks();
+        block_masks::<B, O>(range).any(|(block_index, mask)| blocks[block_index] & mask != B::ZERO)
+    }
+
+    /// Whether all bits in `range` are set. `true` for an empty `range`.
+    pub fn all_in(&self, range: Range<usize>) -> bool {
+        self.check_range(&range);
+        let blocks = self.used_blocks();
+        block_masks::<B, O>(range).all(|(block_index, mask)| blocks[block_index] & mask == mask)
+    }
+}
@@ -0,0 +1,181 @@
+use super::{num_bits_to_blocks, BitBlock, BitOrder, Lsb0, PackedBoolStorage};
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::vec::Vec;
+
+use crate::with_heap;
+
+/// Return the (0-based, logical) subindex of the `k`-th (0-based) set bit in
+/// `block`.
+/// The caller must ensure that `block` has more than `k` set bits.
+fn select_in_block<B: BitBlock, O: BitOrder>(mut block: B, k: usize) -> usize {
+    debug_assert!((block.count_ones() as usize) > k);
+    for _ in 0..k {
+        block = O::without_first_one(block);
+    }
+    O::first_one(block)
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    /// Number of set (`true`) bits.
+    pub fn count_ones(&self) -> usize {
+        match self.num_blocks() {
+            0 => 0,
+            num_blocks => {
+                B::simd_count_ones(&self.used_blocks()[..num_blocks - 1])
+                    + self.masked_block(num_blocks - 1).count_ones() as usize
+            }
+        }
+    }
+
+    /// Number of clear (`false`) bits.
+    pub fn count_zeros(&self) -> usize {
+        self.num_bits - self.count_ones()
+    }
+
+    /// Number of set bits before `index` (that is, in `0..index`). `index` can
+    /// be anything from 0 to `len()` (inclusive).
+    pub fn rank(&self, index: usize) -> usize {
+        assert!(
+            index <= self.num_bits,
+            "Index {index} out of range for {} bits.",
+            self.num_bits
+        );
+        let full_blocks = index / B::BLOCK_BITS;
+        let ones: usize = self.used_blocks()[..full_blocks]
+            .iter()
+            .map(|block| block.count_ones() as usize)
+            .sum();
+        let bits_in_partial = index % B::BLOCK_BITS;
+        if bits_in_partial == 0 {
+            ones
+        } else {
+            let partial = self.used_blocks()[full_blocks] & O::prefix_mask::<B>(bits_in_partial);
+            ones + partial.count_ones() as usize
+        }
+    }
+
+    /// Index of the `k`-th (0-based) set bit, or `None` if there are no more
+    /// than `k` set bits.
+    pub fn select(&self, k: usize) -> Option<usize> {
+        self.select_from_block(0, k)
+    }
+
+    /// Like `select`, but start scanning at `start_block` and count `k` from
+    /// there.
+    fn select_from_block(&self, start_block: usize, mut k: usize) -> Option<usize> {
+        for block_index in start_block..self.num_blocks() {
+            let block = self.masked_block(block_index);
+            let ones = block.count_ones() as usize;
+            if k < ones {
+                return Some(block_index * B::BLOCK_BITS + select_in_block::<B, O>(block, k));
+            }
+            k -= ones;
+        }
+        None
+    }
+}
+
+with_heap! {
+    /// Number of bits covered by one entry of `RankSelectIndex`'s directory.
+    pub const SUPERBLOCK_BITS: usize = 512;
+}
+
+with_heap! {
+    /// Precomputed rank directory over a `PackedBoolStorage`. It makes `rank` a
+    /// constant-time operation, and `select` a binary search plus a scan of one
+    /// superblock (of `SUPERBLOCK_BITS`). It borrows the storage, so the storage
+    /// can't change while this index exists.
+    pub struct RankSelectIndex<'s, 'a, B: BitBlock, const N: usize, O: BitOrder = Lsb0>
+    where
+        [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+    {
+        storage: &'s PackedBoolStorage<'a, B, N, O>,
+        /// Number of set bits before each superblock. It has one more entry
+        /// (at the end) than there are superblocks: the total number of set
+        /// bits.
+        superblock_ranks: Vec<usize>,
+    }
+}
+
+with_heap! {
+    impl<'s, 'a, B: BitBlock, const N: usize, O: BitOrder> RankSelectIndex<'s, 'a, B, N, O>
+    where
+        [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+    {
+        const BLOCKS_PER_SUPERBLOCK: usize = if SUPERBLOCK_BITS > B::BLOCK_BITS {
+            SUPERBLOCK_BITS / B::BLOCK_BITS
+        } else {
+            1
+        };
+
+        pub fn new(storage: &'s PackedBoolStorage<'a, B, N, O>) -> Self {
+            let num_blocks = storage.num_blocks();
+            let mut superblock_ranks = Vec::with_capacity(
+                num_bits_to_blocks(num_blocks, Self::BLOCKS_PER_SUPERBLOCK) + 1,
+            );
+            let mut ones = 0;
+            for block_index in 0..num_blocks {
+                if block_index % Self::BLOCKS_PER_SUPERBLOCK == 0 {
+                    superblock_ranks.push(ones);
+                }
+                ones += storage.masked_block(block_index).count_ones() as usize;
+            }
+            superblock_ranks.push(ones);
+            Self {
+                storage,
+                superblock_ranks,
+            }
+        }
+
+        pub fn storage(&self) -> &'s PackedBoolStorage<'a, B, N, O> {
+            self.storage
+        }
+
+        pub fn count_ones(&self) -> usize {
+            *self.superblock_ranks.last().unwrap()
+        }
+
+        pub fn count_zeros(&self) -> usize {
+            self.storage.num_bits - self.count_ones()
+        }
+
+        /// Like `PackedBoolStorage::rank`.
+        pub fn rank(&self, index: usize) -> usize {
+            assert!(
+                index <= self.storage.num_bits,
+                "Index {index} out of range for {} bits.",
+                self.storage.num_bits
+            );
+            let block_index = index / B::BLOCK_BITS;
+            let superblock = block_index / Self::BLOCKS_PER_SUPERBLOCK;
+            let blocks = self.storage.used_blocks();
+            let mut ones = self.superblock_ranks[superblock];
+            for block in &blocks[superblock * Self::BLOCKS_PER_SUPERBLOCK..block_index] {
+                ones += block.count_ones() as usize;
+            }
+            let bits_in_partial = index % B::BLOCK_BITS;
+            if bits_in_partial != 0 {
+                ones += (blocks[block_index] & O::prefix_mask::<B>(bits_in_partial)).count_ones() as usize;
+            }
+            ones
+        }
+
+        /// Like `PackedBoolStorage::select`.
+        pub fn select(&self, k: usize) -> Option<usize> {
+            if k >= self.count_ones() {
+                return None;
+            }
+            // The last superblock whose rank is not more than `k`.
+            let superblock = self.superblock_ranks.partition_point(|&rank| rank <= k) - 1;
+            self.storage.select_from_block(
+                superblock * Self::BLOCKS_PER_SUPERBLOCK,
+                k - self.superblock_ranks[superblock],
+            )
+        }
+    }
+}
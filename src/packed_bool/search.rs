@@ -0,0 +1,168 @@
+use super::{num_bits_to_blocks, BitBlock, BitOrder, Lsb0, PackedBoolStorage};
+use core::marker::PhantomData;
+
+/// Return block at `block_index` of `blocks` (which hold `num_bits` logical
+/// bits), XOR-ed with `flip`, and with any padding bits cleared. Use `flip` of
+/// `B::ZERO` to scan for set bits, and `B::ONES` to scan for clear bits.
+fn scan_block<B: BitBlock, O: BitOrder>(
+    blocks: &[B],
+    num_bits: usize,
+    block_index: usize,
+    flip: B,
+) -> B {
+    let block = blocks[block_index] ^ flip;
+    let bits_before = block_index * B::BLOCK_BITS;
+    if num_bits - bits_before < B::BLOCK_BITS {
+        block & O::prefix_mask::<B>(num_bits - bits_before)
+    } else {
+        block
+    }
+}
+
+impl<'a, B: BitBlock, const N: usize, O: BitOrder> PackedBoolStorage<'a, B, N, O>
+where
+    [(); num_bits_to_blocks(N, B::BLOCK_BITS)]:,
+{
+    /// Iterate over indices of set (`true`) bits, in ascending order. It skips
+    /// whole clear blocks.
+    pub fn iter_ones(&self) -> PackedBoolIndexIter<'_, B, O> {
+        PackedBoolIndexIter::new(self.used_blocks(), self.num_bits, B::ZERO)
+    }
+
+    /// Iterate over indices of clear (`false`) bits, in ascending order. It
+    /// skips whole blocks with all bits set.
+    pub fn iter_zeros(&self) -> PackedBoolIndexIter<'_, B, O> {
+        PackedBoolIndexIter::new(self.used_blocks(), self.num_bits, B::ONES)
+    }
+
+    /// Index of the first bit at or after `from`, that differs from `flip`.
+    fn next_flipped(&self, from: usize, flip: B) -> Option<usize> {
+        if from >= self.num_bits {
+            return None;
+        }
+        let blocks = self.used_blocks();
+        let mut block_index = from / B::BLOCK_BITS;
+        let mut block = scan_block::<B, O>(blocks, self.num_bits, block_index, flip)
+            & !O::prefix_mask::<B>(from % B::BLOCK_BITS);
+        loop {
+            if block != B::ZERO {
+                return Some(block_index * B::BLOCK_BITS + O::first_one(block));
+            }
+            block_index += 1;
+            if block_index == blocks.len() {
+                return None;
+            }
+            block = scan_block::<B, O>(blocks, self.num_bits, block_index, flip);
+        }
+    }
+
+    /// Index of the last bit at or before `from`, that differs from `flip`.
+    fn prev_flipped(&self, from: usize, flip: B) -> Option<usize> {
+        if self.num_bits == 0 {
+            return None;
+        }
+        let from = from.min(self.num_bits - 1);
+        let blocks = self.used_blocks();
+        let mut block_index = from / B::BLOCK_BITS;
+        let mut block = scan_block::<B, O>(blocks, self.num_bits, block_index, flip)
+            & O::prefix_mask::<B>(from % B::BLOCK_BITS + 1);
+        loop {
+            if block != B::ZERO {
+                return Some(block_index * B::BLOCK_BITS + O::last_one(block));
+            }
+            if block_index == 0 {
+                return None;
+            }
+            block_index -= 1;
+            block = scan_block::<B, O>(blocks, self.num_bits, block_index, flip);
+        }
+    }
+
+    /// Index of the first set bit at or after `from`.
+    pub fn next_one(&self, from: usize) -> Option<usize> {
+        self.next_flipped(from, B::ZERO)
+    }
+    /// Index of the first clear bit at or after `from`.
+    pub fn next_zero(&self, from: usize) -> Option<usize> {
+        self.next_flipped(from, B::ONES)
+    }
+    /// Index of the last set bit at or before `from`. If `from` is out of
+    /// range, search from the last bit.
+    pub fn prev_one(&self, from: usize) -> Option<usize> {
+        self.prev_flipped(from, B::ZERO)
+    }
+    /// Index of the last clear bit at or before `from`. If `from` is out of
+    /// range, search from the last bit.
+    pub fn prev_zero(&self, from: usize) -> Option<usize> {
+        self.prev_flipped(from, B::ONES)
+    }
+
+    pub fn first_one(&self) -> Option<usize> {
+        self.next_one(0)
+    }
+    pub fn first_zero(&self) -> Option<usize> {
+        self.next_zero(0)
+    }
+    pub fn last_one(&self) -> Option<usize> {
+        self.prev_one(usize::MAX)
+    }
+    pub fn last_zero(&self) -> Option<usize> {
+        self.prev_zero(usize::MAX)
+    }
+}
+
+/// Iterate over indices of set bits (or of clear bits) of a
+/// `PackedBoolStorage`. See `PackedBoolStorage::iter_ones` and
+/// `PackedBoolStorage::iter_zeros`.
+#[derive(Debug, Clone)]
+pub struct PackedBoolIndexIter<'s, B: BitBlock, O: BitOrder = Lsb0> {
+    blocks: &'s [B],
+    num_bits: usize,
+    /// `B::ZERO` when iterating over set bits, `B::ONES` for clear bits.
+    flip: B,
+    /// Index of `current` block.
+    block_index: usize,
+    /// Remaining (not yet returned) bits of the current block, already
+    /// flipped & masked.
+    current: B,
+    bit_order: PhantomData<O>,
+}
+
+impl<'s, B: BitBlock, O: BitOrder> PackedBoolIndexIter<'s, B, O> {
+    fn new(blocks: &'s [B], num_bits: usize, flip: B) -> Self {
+        let current = if blocks.is_empty() {
+            B::ZERO
+        } else {
+            scan_block::<B, O>(blocks, num_bits, 0, flip)
+        };
+        Self {
+            blocks,
+            num_bits,
+            flip,
+            block_index: 0,
+            current,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<'s, B: BitBlock, O: BitOrder> Iterator for PackedBoolIndexIter<'s, B, O> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.current == B::ZERO {
+            if self.block_index + 1 >= self.blocks.len() {
+                return None;
+            }
+            self.block_index += 1;
+            self.current =
+                scan_block::<B, O>(self.blocks, self.num_bits, self.block_index, self.flip);
+        }
+        let index = self.block_index * B::BLOCK_BITS + O::first_one(self.current);
+        self.current = O::without_first_one(self.current);
+        Some(index)
+    }
+}
+
+impl<'s, B: BitBlock, O: BitOrder> core::iter::FusedIterator for PackedBoolIndexIter<'s, B, O> {}
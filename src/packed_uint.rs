@@ -0,0 +1,559 @@
+use crate::abstra::NewEmptyLike;
+use crate::byte_slice::num_bits_to_bytes;
+use crate::error::{self, Error};
+use crate::slices::{
+    ByteSlice, SliceBacked, SliceBackedChoice, SliceDefault, SliceRead, SliceWrite,
+};
+use crate::{match_cfg, with_heap};
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::vec::Vec;
+
+/// Unsigned integer type that holds values of `PackedUintStorage`.
+pub trait PackedUint: Clone + Copy + PartialEq + Default + Debug {
+    /// Maximum number of bits per value that this type can hold.
+    const VALUE_BITS: usize;
+    fn to_u64(self) -> u64;
+    /// Narrow `value`. The caller ensures that it fits.
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! packed_uint_impl {
+    ($($uint:ty),+) => {
+        $(
+            impl PackedUint for $uint {
+                const VALUE_BITS: usize = <$uint>::BITS as usize;
+                #[inline]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+                #[inline]
+                fn from_u64(value: u64) -> Self {
+                    value as Self
+                }
+            }
+        )+
+    };
+}
+packed_uint_impl!(u8, u16, u32);
+
+/// A value of `PackedUintStorage`, returned by value (rather than by
+/// reference, as there is no unpacked value to refer to). Its `Deref` lets
+/// `SliceRead::iter` yield it where slice-backed storages yield `&T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedUintItem<T: PackedUint>(pub T);
+
+impl<T: PackedUint> Deref for PackedUintItem<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Number of bytes for `num_values` values of `bits` bits each. (A function,
+/// rather than `num_bits_to_bytes(num_values * bits)` in const generic
+/// bounds, so that `NARR`'s bound doesn't multiply by a literal `0`.)
+pub const fn num_values_to_bytes(num_values: usize, bits: usize) -> usize {
+    num_bits_to_bytes(num_values * bits)
+}
+
+/// Read `num_bits` (up to 32) bits starting at bit `bit_offset` of `bytes`
+/// (least significant bit first).
+fn read_bits(bytes: &[u8], bit_offset: usize, num_bits: usize) -> u64 {
+    let first_byte = bit_offset / 8;
+    let shift = bit_offset % 8;
+    let num_bytes = num_bits_to_bytes(shift + num_bits);
+    let mut result = 0u64;
+    for (i, byte) in bytes[first_byte..first_byte + num_bytes].iter().enumerate() {
+        result |= (*byte as u64) << (8 * i);
+    }
+    (result >> shift) & ((1 << num_bits) - 1)
+}
+
+/// Write `num_bits` (up to 32) bits of `value` starting at bit `bit_offset` of
+/// `bytes` (least significant bit first). Leave any other bits unchanged.
+fn write_bits(bytes: &mut [u8], bit_offset: usize, num_bits: usize, value: u64) {
+    let first_byte = bit_offset / 8;
+    let shift = bit_offset % 8;
+    let num_bytes = num_bits_to_bytes(shift + num_bits);
+    let mask = ((1u64 << num_bits) - 1) << shift;
+    let value = value << shift;
+    for (i, byte) in bytes[first_byte..first_byte + num_bytes]
+        .iter_mut()
+        .enumerate()
+    {
+        let byte_mask = (mask >> (8 * i)) as u8;
+        *byte = (*byte & !byte_mask) | (value >> (8 * i)) as u8;
+    }
+}
+
+/// "Packed" storage of unsigned integers, `BITS` (1 to 32) bits per value,
+/// densely in a `ByteSlice`. Values are of type `T` (`u8`, `u16` or `u32`),
+/// which must be able to hold `BITS` bits. See `crate::slices::SliceStorage`
+/// for const generic parameter `N` (here it's the number of values for
+/// array-based instances).
+#[derive(Clone)]
+pub struct PackedUintStorage<'a, T: PackedUint, const BITS: usize, const N: usize>
+where
+    Self: 'a,
+    [(); num_values_to_bytes(N, BITS)]:,
+{
+    byte_slice: ByteSlice<'a, { num_values_to_bytes(N, BITS) }>,
+    /// Number of values. Equal to `N` if array-based (except for copies of
+    /// fewer values made without heap, which leave the rest of the array
+    /// unused).
+    num_values: usize,
+    value_type: PhantomData<T>,
+}
+
+impl<'a, T: PackedUint, const BITS: usize, const N: usize> PackedUintStorage<'a, T, BITS, N>
+where
+    [(); num_values_to_bytes(N, BITS)]:,
+{
+    /// Evaluated (and hence checked at compile time) by constructors.
+    const VALID_BITS: () = assert!(
+        BITS > 0 && BITS <= 32 && BITS <= T::VALUE_BITS,
+        "BITS must be from 1 to 32, and must fit in T."
+    );
+    /// Largest value that fits in `BITS` bits.
+    pub const MAX: u64 = (1 << BITS) - 1;
+
+    /// Interpret `byte_slice` (which can be any variant of `ByteSlice`) as
+    /// `num_values` packed values.
+    pub fn from_byte_slice(
+        byte_slice: ByteSlice<'a, { num_values_to_bytes(N, BITS) }>,
+        num_values: usize,
+    ) -> Self {
+        let () = Self::VALID_BITS;
+        assert!(
+            byte_slice.shared_slice().len() >= num_bits_to_bytes(num_values * BITS),
+            "Not enough bytes for {num_values} values."
+        );
+        Self {
+            byte_slice,
+            num_values,
+            value_type: PhantomData,
+        }
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.num_values
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_values == 0
+    }
+
+    /// Backing bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.byte_slice.shared_slice()
+    }
+
+    /// Iterate over values (rather than over `PackedUintItem`s, like
+    /// `SliceRead::iter` does).
+    pub fn values(&self) -> PackedUintValues<'_, T, BITS> {
+        PackedUintValues {
+            bytes: self.byte_slice.shared_slice(),
+            front: 0,
+            back: self.num_values,
+            value_type: PhantomData,
+        }
+    }
+
+    /// Panic if `index` is out of range.
+    fn check_index(&self, index: usize) {
+        assert!(
+            index < self.num_values,
+            "Index {index} out of range for {} values.",
+            self.num_values
+        );
+    }
+
+    /// Get the value at `index`.
+    pub fn get_value(&self, index: usize) -> T {
+        self.check_index(index);
+        T::from_u64(read_bits(
+            self.byte_slice.shared_slice(),
+            index * BITS,
+            BITS,
+        ))
+    }
+
+    /// Set the value at `index`. Panic if `value` doesn't fit in `BITS` bits.
+    /// Return true if the value was different.
+    pub fn set_value(&mut self, index: usize, value: T) -> bool {
+        self.try_set_value(index, value)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like `set_value`, but return an error (rather than panic) if `index` is
+    /// out of range, if `value` doesn't fit, or if `self` is Shared-based.
+    pub fn try_set_value(&mut self, index: usize, value: T) -> Result<bool, Error> {
+        error::check_index(index, self.num_values)?;
+        let value = value.to_u64();
+        if value > Self::MAX {
+            return Err(Error::ValueDoesNotFit { value, bits: BITS });
+        }
+        let bytes = self.byte_slice.try_mutable_slice()?;
+        let old_value = read_bits(bytes, index * BITS, BITS);
+        write_bits(bytes, index * BITS, BITS, value);
+        Ok(old_value != value)
+    }
+
+    /// Pack `N` values from `values` into a new array-based instance. Panic if
+    /// `values` has fewer than `N` items (like `SliceStorage` does).
+    fn pack_to_array(values: impl Iterator<Item = T>) -> Self {
+        let mut array = [0u8; num_values_to_bytes(N, BITS)];
+        let mut num_values = 0;
+        for value in values.take(N) {
+            let value = value.to_u64();
+            assert!(
+                value <= Self::MAX,
+                "Value {value} doesn't fit in {BITS} bits."
+            );
+            write_bits(&mut array, num_values * BITS, BITS, value);
+            num_values += 1;
+        }
+        assert!(
+            num_values == N,
+            "Expecting {N} items, but got {num_values} only."
+        );
+        Self::from_byte_slice(ByteSlice::from_array(array), N)
+    }
+
+    with_heap! {
+        /// Pack (all) `values` into a new `Vec`-based instance.
+        fn pack_to_vec(values: impl Iterator<Item = T>) -> Self {
+            let mut vec = Vec::new();
+            let mut num_values = 0;
+            for value in values {
+                vec.resize(num_bits_to_bytes((num_values + 1) * BITS), 0);
+                let value = value.to_u64();
+                assert!(value <= Self::MAX, "Value {value} doesn't fit in {BITS} bits.");
+                write_bits(&mut vec, num_values * BITS, BITS, value);
+                num_values += 1;
+            }
+            Self::from_byte_slice(ByteSlice::from_vec(vec), num_values)
+        }
+    }
+
+    /// Pack a copy of `slice`. Array-based if it has `N` items, otherwise
+    /// `Vec`-based. Without heap it's array-based with up to `N` items, and
+    /// more items return `Error::LengthMismatch`. Return
+    /// `Error::ValueDoesNotFit` if any value is too wide.
+    fn try_pack_from_slice(slice: &[T]) -> Result<Self, Error> {
+        if let Some(value) = slice
+            .iter()
+            .map(|value| value.to_u64())
+            .find(|value| *value > Self::MAX)
+        {
+            return Err(Error::ValueDoesNotFit { value, bits: BITS });
+        }
+        if slice.len() == N {
+            return Ok(Self::pack_to_array(slice.iter().cloned()));
+        }
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+        return Ok(Self::pack_to_vec(slice.iter().cloned()));
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        {
+            if slice.len() > N {
+                return Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: slice.len(),
+                });
+            }
+            let mut array = [0u8; num_values_to_bytes(N, BITS)];
+            for (index, value) in slice.iter().enumerate() {
+                write_bits(&mut array, index * BITS, BITS, value.to_u64());
+            }
+            Ok(Self::from_byte_slice(
+                ByteSlice::from_array(array),
+                slice.len(),
+            ))
+        }
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize, const N: usize> SliceRead<T>
+    for PackedUintStorage<'a, T, BITS, N>
+where
+    [(); num_values_to_bytes(N, BITS)]:,
+{
+    type ITER<'s>
+        = core::iter::Map<PackedUintValues<'s, T, BITS>, fn(T) -> PackedUintItem<T>>
+    where
+        Self: 's;
+
+    fn len(&self) -> usize {
+        self.num_values
+    }
+
+    fn get(&self, index: usize) -> T {
+        self.get_value(index)
+    }
+    fn iter<'s>(&'s self) -> Self::ITER<'s> {
+        self.values().map(PackedUintItem)
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize, const N: usize> SliceWrite<T>
+    for PackedUintStorage<'a, T, BITS, N>
+where
+    [(); num_values_to_bytes(N, BITS)]:,
+{
+    fn set(&mut self, index: usize, value: &T) {
+        self.set_value(index, *value);
+    }
+    fn try_set(&mut self, index: usize, value: &T) -> Result<(), Error> {
+        self.try_set_value(index, *value).map(|_| ())
+    }
+    fn check_and_set(&mut self, index: usize, value: &T) -> bool {
+        self.set_value(index, *value)
+    }
+    fn try_check_and_set(&mut self, index: usize, value: &T) -> Result<bool, Error> {
+        self.try_set_value(index, *value)
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize, const N: usize> SliceDefault<'a, T, N>
+    for PackedUintStorage<'a, T, BITS, N>
+where
+    [(); num_values_to_bytes(N, BITS)]:,
+    [(); num_values_to_bytes(0, BITS)]:,
+{
+    with_heap! {
+        type NARR = PackedUintStorage<'a, T, BITS, 0>;
+    }
+
+    // Ownership transfer constructors. They can't transfer ownership of
+    // unpacked values, so `from_shared` packs a copy instead. Writes to a copy
+    // wouldn't reach `slice` or `vector`, so `from_mutable` and `from_vec_ref`
+    // panic (and their `try_*` counterparts return `Error::NotSliceBacked`).
+    // Use `from_byte_slice` with a Mutable-based `ByteSlice` to modify packed
+    // values in place.
+    fn from_shared(slice: &'a [T]) -> Self {
+        Self::try_from_shared(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_shared(slice: &'a [T]) -> Result<Self, Error> {
+        Self::try_pack_from_slice(slice)
+    }
+    fn from_mutable(slice: &'a mut [T]) -> Self {
+        Self::try_from_mutable(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_mutable(_slice: &'a mut [T]) -> Result<Self, Error> {
+        Err(Error::NotSliceBacked)
+    }
+    fn from_array(array: [T; N]) -> Self {
+        Self::pack_to_array(array.into_iter())
+    }
+
+    with_heap! {
+        fn from_vec(vector: Vec<T>) -> Self {
+            Self::pack_to_vec(vector.into_iter())
+        }
+    }
+    with_heap! {
+        fn from_vec_ref(vector: &'a mut Vec<T>) -> Self {
+            Self::try_from_vec_ref(vector).unwrap_or_else(|error| panic!("{error}"))
+        }
+    }
+    with_heap! {
+        fn try_from_vec_ref(_vector: &'a mut Vec<T>) -> Result<Self, Error> {
+            Err(Error::NotSliceBacked)
+        }
+    }
+
+    fn from_value_to_array(value_ref: &T) -> Self {
+        Self::pack_to_array(core::iter::repeat(*value_ref))
+    }
+    with_heap! {
+        fn from_value_to_vec(value: &T, size: usize) -> Self {
+            Self::pack_to_vec(core::iter::repeat_n(*value, size))
+        }
+    }
+
+    fn from_iter_to_array(iter: impl Iterator<Item = T>) -> Self {
+        Self::pack_to_array(iter)
+    }
+    with_heap! {
+        fn from_iter_to_vec(iter: impl Iterator<Item = T>) -> Self {
+            Self::pack_to_vec(iter)
+        }
+    }
+
+    fn from_fn_to_array(f: impl FnMut() -> T) -> Self {
+        Self::pack_to_array(core::iter::repeat_with(f))
+    }
+    with_heap! {
+        fn from_fn_to_vec(f: impl FnMut() -> T, size: usize) -> Self {
+            Self::pack_to_vec(core::iter::repeat_with(f).take(size))
+        }
+    }
+
+    fn from_default(size: usize, storage_type: SliceBackedChoice) -> Self {
+        Self::try_from_default(size, storage_type).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_default(size: usize, storage_type: SliceBackedChoice) -> Result<Self, Error> {
+        let num_values = if storage_type.is_array() { N } else { size };
+        Ok(Self::from_byte_slice(
+            ByteSlice::try_from_default(num_bits_to_bytes(size * BITS), storage_type)?,
+            num_values,
+        ))
+    }
+    fn from_default_to_array() -> Self {
+        Self::from_byte_slice(ByteSlice::from_default_to_array(), N)
+    }
+    with_heap! {
+        fn from_default_to_vec(size: usize) -> Self {
+            Self::from_byte_slice(
+                ByteSlice::from_default_to_vec(num_bits_to_bytes(size * BITS)),
+                size,
+            )
+        }
+    }
+
+    fn to_array_based(&self) -> Self {
+        Self::pack_to_array(self.values())
+    }
+
+    with_heap! {
+        fn to_vec_based(self) -> Self {
+            let num_values = self.num_values;
+            Self::from_byte_slice(self.byte_slice.to_vec_based(), num_values)
+        }
+    }
+    with_heap! {
+        fn to_non_array_vec_based(&self) -> Self::NARR {
+            // Not delegating to `ByteSlice::to_non_array_vec_based()`, because
+            // the compiler can't unify its `0` with NARR's
+            // `num_values_to_bytes(0, BITS)`.
+            Self::NARR::from_byte_slice(
+                ByteSlice::from_vec(self.byte_slice.shared_slice().to_vec()),
+                self.num_values,
+            )
+        }
+    }
+
+    with_heap! {
+        fn try_from_non_array_vec_based(from: &Self::NARR,
+            as_choice: &SliceBackedChoice
+        ) -> Result<Self, Error> {
+            let from_bytes = from.byte_slice.shared_slice();
+            match as_choice {
+                SliceBackedChoice::Array if from.num_values != N => Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: from.num_values,
+                }),
+                SliceBackedChoice::Array => Ok(Self::from_byte_slice(
+                    ByteSlice::from_array(core::array::from_fn(|i| from_bytes[i])),
+                    N,
+                )),
+                SliceBackedChoice::Inline
+                | SliceBackedChoice::Vec
+                | SliceBackedChoice::Boxed
+                | SliceBackedChoice::Rc
+                | SliceBackedChoice::Arc => Ok(Self::from_byte_slice(
+                    ByteSlice::try_from_iter_to(from_bytes.iter().copied(), from_bytes.len(), *as_choice)?,
+                    from.num_values,
+                )),
+                SliceBackedChoice::Shared | SliceBackedChoice::Mutable | SliceBackedChoice::VecRef => {
+                    Err(Error::UnsupportedChoice(*as_choice))
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize, const N: usize> NewEmptyLike
+    for PackedUintStorage<'a, T, BITS, N>
+where
+    Self: 'a,
+    [(); num_values_to_bytes(N, BITS)]:,
+{
+    fn new_empty_like(&self) -> Self {
+        let byte_slice = self.byte_slice.new_empty_like();
+        // Inline-based and Vec-based instances become empty. Others keep
+        // their size.
+        let num_values = match_cfg! {&byte_slice,
+            ByteSlice::Inline(_) => 0,
+            ~[heap~]
+            ByteSlice::Vec(_) => 0,
+            _ => self.num_values
+        };
+        Self {
+            byte_slice,
+            num_values,
+            value_type: PhantomData,
+        }
+    }
+}
+
+/// Iterate over (logical) values, returning them by value.
+#[derive(Debug, Clone)]
+pub struct PackedUintValues<'a, T: PackedUint, const BITS: usize> {
+    bytes: &'a [u8],
+    /// Index of the next value to return from the front.
+    front: usize,
+    /// Index right after the next value to return from the back.
+    back: usize,
+    value_type: PhantomData<T>,
+}
+
+impl<'a, T: PackedUint, const BITS: usize> PackedUintValues<'a, T, BITS> {
+    fn value(&self, index: usize) -> T {
+        T::from_u64(read_bits(self.bytes, index * BITS, BITS))
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize> Iterator for PackedUintValues<'a, T, BITS> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.front < self.back {
+            let result = self.value(self.front);
+            self.front += 1;
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize> DoubleEndedIterator for PackedUintValues<'a, T, BITS> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.value(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: PackedUint, const BITS: usize> ExactSizeIterator for PackedUintValues<'a, T, BITS> {}
+
+impl<'a, T: PackedUint, const BITS: usize> core::iter::FusedIterator
+    for PackedUintValues<'a, T, BITS>
+{
+}
@@ -0,0 +1,871 @@
+use crate::abstra::NewEmptyLike;
+use crate::error::Error;
+use crate::slices::{SliceBackedChoice, SliceDefault, SliceRead, SliceWrite};
+use core::iter::Peekable;
+use core::ops::{BitAnd, BitOr};
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::{boxed::Box, vec::Vec};
+
+/// Number of bits covered by one chunk (and its container): 64K.
+pub const CHUNK_BITS: usize = 1 << 16;
+/// Number of `u64` words in a bitmap container.
+const BITMAP_WORDS: usize = CHUNK_BITS / 64;
+/// Maximum number of set bits in an array container. With more, a bitmap
+/// container (8 KiB) is smaller.
+const ARRAY_MAX: usize = 4096;
+/// Maximum number of runs in a run container. With more, a bitmap container
+/// is smaller.
+const RUNS_MAX: usize = 2048;
+
+/// Inclusive range of set bits, within a chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Run {
+    start: u16,
+    last: u16,
+}
+
+/// Set bits of one (non-empty) chunk. The lower 16 bits of their indices.
+#[derive(Clone, Debug)]
+enum Container {
+    /// Sorted. At most `ARRAY_MAX` items.
+    Array(Vec<u16>),
+    /// One bit per index, least significant bit first, plus the number of set
+    /// bits.
+    Bitmap(Box<[u64; BITMAP_WORDS]>, usize),
+    /// Sorted, with no overlapping or adjacent runs.
+    Run(Vec<Run>),
+}
+
+impl Container {
+    fn cardinality(&self) -> usize {
+        match self {
+            Container::Array(array) => array.len(),
+            Container::Bitmap(_, cardinality) => *cardinality,
+            Container::Run(runs) => runs
+                .iter()
+                .map(|run| (run.last - run.start) as usize + 1)
+                .sum(),
+        }
+    }
+
+    /// Approximate size (in bytes) of the container's heap allocation.
+    fn heap_size(&self) -> usize {
+        match self {
+            Container::Array(array) => array.len() * 2,
+            Container::Bitmap(_, _) => BITMAP_WORDS * 8,
+            Container::Run(runs) => runs.len() * 4,
+        }
+    }
+
+    /// Index of the first run that ends at or after `low`.
+    fn run_position(runs: &[Run], low: u16) -> usize {
+        runs.partition_point(|run| run.last < low)
+    }
+
+    fn contains(&self, low: u16) -> bool {
+        match self {
+            Container::Array(array) => array.binary_search(&low).is_ok(),
+            Container::Bitmap(words, _) => words[low as usize / 64] & (1 << (low % 64)) != 0,
+            Container::Run(runs) => {
+                let position = Self::run_position(runs, low);
+                position < runs.len() && runs[position].start <= low
+            }
+        }
+    }
+
+    /// Set bit `low`. Return true if it was clear.
+    fn insert(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(array) => match array.binary_search(&low) {
+                Ok(_) => return false,
+                Err(position) => array.insert(position, low),
+            },
+            Container::Bitmap(words, cardinality) => {
+                let mask = 1 << (low % 64);
+                let word = &mut words[low as usize / 64];
+                if *word & mask != 0 {
+                    return false;
+                }
+                *word |= mask;
+                *cardinality += 1;
+            }
+            Container::Run(runs) => {
+                let position = Self::run_position(runs, low);
+                if position < runs.len() && runs[position].start <= low {
+                    return false;
+                }
+                let joins_previous =
+                    position > 0 && runs[position - 1].last as u32 + 1 == low as u32;
+                let joins_next =
+                    position < runs.len() && runs[position].start as u32 == low as u32 + 1;
+                match (joins_previous, joins_next) {
+                    (true, true) => {
+                        runs[position - 1].last = runs[position].last;
+                        runs.remove(position);
+                    }
+                    (true, false) => runs[position - 1].last = low,
+                    (false, true) => runs[position].start = low,
+                    (false, false) => runs.insert(
+                        position,
+                        Run {
+                            start: low,
+                            last: low,
+                        },
+                    ),
+                }
+            }
+        }
+        self.rebalance();
+        true
+    }
+
+    /// Clear bit `low`. Return true if it was set.
+    fn remove(&mut self, low: u16) -> bool {
+        match self {
+            Container::Array(array) => match array.binary_search(&low) {
+                Ok(position) => {
+                    array.remove(position);
+                }
+                Err(_) => return false,
+            },
+            Container::Bitmap(words, cardinality) => {
+                let mask = 1 << (low % 64);
+                let word = &mut words[low as usize / 64];
+                if *word & mask == 0 {
+                    return false;
+                }
+                *word &= !mask;
+                *cardinality -= 1;
+            }
+            Container::Run(runs) => {
+                let position = Self::run_position(runs, low);
+                if position == runs.len() || runs[position].start > low {
+                    return false;
+                }
+                let run = runs[position];
+                if run.start == run.last {
+                    runs.remove(position);
+                } else if low == run.start {
+                    runs[position].start += 1;
+                } else if low == run.last {
+                    runs[position].last -= 1;
+                } else {
+                    runs[position].last = low - 1;
+                    runs.insert(
+                        position + 1,
+                        Run {
+                            start: low + 1,
+                            last: run.last,
+                        },
+                    );
+                }
+            }
+        }
+        self.rebalance();
+        true
+    }
+
+    /// Switch an array container that grew too big, a bitmap container that
+    /// shrank enough, or a run container that has too many runs.
+    fn rebalance(&mut self) {
+        let switch = match self {
+            Container::Array(array) => array.len() > ARRAY_MAX,
+            Container::Bitmap(_, cardinality) => *cardinality <= ARRAY_MAX,
+            Container::Run(runs) => runs.len() > RUNS_MAX,
+        };
+        if switch {
+            *self = Self::from_words(self.to_words());
+        }
+    }
+
+    fn to_words(&self) -> Box<[u64; BITMAP_WORDS]> {
+        if let Container::Bitmap(words, _) = self {
+            return words.clone();
+        }
+        let mut words = Box::new([0; BITMAP_WORDS]);
+        for low in self.iter() {
+            words[low as usize / 64] |= 1 << (low % 64);
+        }
+        words
+    }
+
+    /// Array or bitmap container, whichever is smaller.
+    fn from_words(words: Box<[u64; BITMAP_WORDS]>) -> Self {
+        let cardinality = words.iter().map(|word| word.count_ones() as usize).sum();
+        if cardinality <= ARRAY_MAX {
+            Container::Array(WordsIter::new(&words[..]).collect())
+        } else {
+            Container::Bitmap(words, cardinality)
+        }
+    }
+
+    /// Convert to the smallest kind of container (which may be a run one).
+    fn optimized(&self) -> Self {
+        let mut runs = Vec::new();
+        for low in self.iter() {
+            match runs.last_mut() {
+                Some(Run { last, .. }) if *last as u32 + 1 == low as u32 => *last = low,
+                _ => runs.push(Run {
+                    start: low,
+                    last: low,
+                }),
+            }
+        }
+        let cardinality = self.cardinality();
+        if runs.len() * 4 < (cardinality * 2).min(BITMAP_WORDS * 8) {
+            Container::Run(runs)
+        } else {
+            Self::from_words(self.to_words())
+        }
+    }
+
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Container::Array(array) => ContainerIter::Array(array.iter()),
+            Container::Bitmap(words, _) => ContainerIter::Bitmap(WordsIter::new(&words[..])),
+            Container::Run(runs) => ContainerIter::Run(runs.iter(), 1..1),
+        }
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Container::Array(left), Container::Array(right)) => {
+                let mut array = Vec::with_capacity(left.len() + right.len());
+                let (mut left, mut right) = (left.iter().peekable(), right.iter().peekable());
+                loop {
+                    let low = match (left.peek(), right.peek()) {
+                        (Some(l), Some(r)) if l < r => left.next(),
+                        (Some(l), Some(r)) if l > r => right.next(),
+                        (Some(_), Some(_)) => {
+                            right.next();
+                            left.next()
+                        }
+                        (Some(_), None) => left.next(),
+                        (None, _) => right.next(),
+                    };
+                    match low {
+                        Some(low) => array.push(*low),
+                        None => break,
+                    }
+                }
+                let mut container = Container::Array(array);
+                container.rebalance();
+                container
+            }
+            (Container::Run(left), Container::Run(right)) => {
+                let mut all = left.iter().chain(right.iter()).copied().collect::<Vec<_>>();
+                all.sort_unstable_by_key(|run| run.start);
+                let mut runs = Vec::<Run>::with_capacity(all.len());
+                for run in all {
+                    match runs.last_mut() {
+                        Some(previous) if previous.last as u32 + 1 >= run.start as u32 => {
+                            previous.last = previous.last.max(run.last);
+                        }
+                        _ => runs.push(run),
+                    }
+                }
+                let mut container = Container::Run(runs);
+                container.rebalance();
+                container
+            }
+            _ => {
+                let mut words = self.to_words();
+                for (word, other_word) in words.iter_mut().zip(other.to_words().iter()) {
+                    *word |= *other_word;
+                }
+                Self::from_words(words)
+            }
+        }
+    }
+
+    /// Return `None` if empty.
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let container = match (self, other) {
+            (Container::Array(array), other) | (other, Container::Array(array)) => {
+                Container::Array(
+                    array
+                        .iter()
+                        .copied()
+                        .filter(|low| other.contains(*low))
+                        .collect(),
+                )
+            }
+            (Container::Run(left), Container::Run(right)) => {
+                let mut runs = Vec::new();
+                let (mut l, mut r) = (0, 0);
+                while l < left.len() && r < right.len() {
+                    let start = left[l].start.max(right[r].start);
+                    let last = left[l].last.min(right[r].last);
+                    if start <= last {
+                        runs.push(Run { start, last });
+                    }
+                    if left[l].last < right[r].last {
+                        l += 1;
+                    } else {
+                        r += 1;
+                    }
+                }
+                // Intersecting runs can split them, so there may be too many.
+                let mut container = Container::Run(runs);
+                container.rebalance();
+                container
+            }
+            _ => {
+                let mut words = self.to_words();
+                for (word, other_word) in words.iter_mut().zip(other.to_words().iter()) {
+                    *word &= *other_word;
+                }
+                Self::from_words(words)
+            }
+        };
+        if container.cardinality() == 0 {
+            None
+        } else {
+            Some(container)
+        }
+    }
+}
+
+/// Iterate over indices of set bits in `u64` words, least significant bit
+/// first.
+#[derive(Clone, Debug)]
+struct WordsIter<'c> {
+    words: &'c [u64],
+    word_index: usize,
+    /// Remaining (not yet returned) set bits of `words[word_index]`.
+    word: u64,
+}
+
+impl<'c> WordsIter<'c> {
+    fn new(words: &'c [u64]) -> Self {
+        Self {
+            words,
+            word_index: 0,
+            word: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl<'c> Iterator for WordsIter<'c> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        while self.word == 0 {
+            self.word_index += 1;
+            if self.word_index >= self.words.len() {
+                return None;
+            }
+            self.word = self.words[self.word_index];
+        }
+        let low = self.word_index * 64 + self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(low as u16)
+    }
+}
+
+/// Iterate over set bits of a container.
+#[derive(Clone, Debug)]
+enum ContainerIter<'c> {
+    Array(core::slice::Iter<'c, u16>),
+    Bitmap(WordsIter<'c>),
+    /// Remaining runs, and the remaining part of the current run.
+    Run(core::slice::Iter<'c, Run>, core::ops::Range<u32>),
+}
+
+impl<'c> Iterator for ContainerIter<'c> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            ContainerIter::Array(iter) => iter.next().copied(),
+            ContainerIter::Bitmap(iter) => iter.next(),
+            ContainerIter::Run(runs, current) => loop {
+                if let Some(low) = current.next() {
+                    return Some(low as u16);
+                }
+                let run = runs.next()?;
+                *current = run.start as u32..run.last as u32 + 1;
+            },
+        }
+    }
+}
+
+/// Compressed bit storage, in the style of Roaring bitmaps
+/// (https://roaringbitmap.org). Bits are split into chunks of `CHUNK_BITS`
+/// (64K). Each chunk with any set bits has a container: an array of set bits
+/// (for sparse chunks), a bitmap (for dense chunks) or runs of set bits (for
+/// clustered chunks). Hence it suits very sparse, or very clustered, bits that
+/// span a large range (for example the whole `u32` space).
+///
+/// It implements `SliceDefault<'a, bool, N>` (like
+/// `crate::byte_slice::ByteSliceBoolStorage` does). It has no backing
+/// storage to choose: `N` is the number of bits of instances created by
+/// array-based constructors. It requires heap.
+#[derive(Clone, Debug)]
+pub struct RoaringBoolStorage<const N: usize> {
+    /// (Key, container) of chunks with any set bits, sorted by key. Key is the
+    /// index of the chunk (bit index divided by `CHUNK_BITS`).
+    chunks: Vec<(usize, Container)>,
+    /// Logical number of bits. Equal to `N` if created as array-based.
+    num_bits: usize,
+}
+
+impl<const N: usize> RoaringBoolStorage<N> {
+    /// New instance of `num_bits` clear bits.
+    pub fn new(num_bits: usize) -> Self {
+        Self {
+            chunks: Vec::new(),
+            num_bits,
+        }
+    }
+
+    /// New instance of `num_bits` bits, all set to `value`.
+    fn new_filled(num_bits: usize, value: bool) -> Self {
+        let mut storage = Self::new(num_bits);
+        if value {
+            storage.chunks = (0..num_bits.div_ceil(CHUNK_BITS))
+                .map(|key| {
+                    let last = (num_bits - key * CHUNK_BITS).min(CHUNK_BITS) - 1;
+                    let run = Run {
+                        start: 0,
+                        last: last as u16,
+                    };
+                    (key, Container::Run(Vec::from([run])))
+                })
+                .collect();
+        }
+        storage
+    }
+
+    /// Collect (up to `limit` of) `bits`.
+    fn from_bits(bits: impl Iterator<Item = bool>, limit: usize) -> Self {
+        let mut storage = Self::new(0);
+        for (index, bit) in bits.take(limit).enumerate() {
+            storage.num_bits = index + 1;
+            if bit {
+                storage.insert(index);
+            }
+        }
+        storage.run_optimize();
+        storage
+    }
+
+    /// Collect exactly `N` items of `bits`. Panic if there are fewer of them
+    /// (like `crate::slices::SliceStorage` does).
+    fn from_bits_to_array(bits: impl Iterator<Item = bool>) -> Self {
+        let storage = Self::from_bits(bits, N);
+        assert!(
+            storage.num_bits == N,
+            "Expecting {N} items, but got {} only.",
+            storage.num_bits
+        );
+        storage
+    }
+
+    /// Number of (logical) bits.
+    pub fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_bits == 0
+    }
+
+    /// Panic if `index` is out of range.
+    fn check_index(&self, index: usize) {
+        assert!(
+            index < self.num_bits,
+            "Index {index} out of range for {} bits.",
+            self.num_bits
+        );
+    }
+
+    fn chunk_position(&self, key: usize) -> Result<usize, usize> {
+        self.chunks
+            .binary_search_by_key(&key, |(chunk_key, _)| *chunk_key)
+    }
+
+    /// Set bit at `index`. Return true if it was clear.
+    fn insert(&mut self, index: usize) -> bool {
+        let (key, low) = (index / CHUNK_BITS, (index % CHUNK_BITS) as u16);
+        match self.chunk_position(key) {
+            Ok(position) => self.chunks[position].1.insert(low),
+            Err(position) => {
+                self.chunks
+                    .insert(position, (key, Container::Array(Vec::from([low]))));
+                true
+            }
+        }
+    }
+
+    /// Clear bit at `index`. Return true if it was set.
+    fn remove(&mut self, index: usize) -> bool {
+        let (key, low) = (index / CHUNK_BITS, (index % CHUNK_BITS) as u16);
+        match self.chunk_position(key) {
+            Ok(position) => {
+                let removed = self.chunks[position].1.remove(low);
+                if self.chunks[position].1.cardinality() == 0 {
+                    self.chunks.remove(position);
+                }
+                removed
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Number of set (`true`) bits (cardinality).
+    pub fn count_ones(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|(_, container)| container.cardinality())
+            .sum()
+    }
+
+    /// Number of clear (`false`) bits.
+    pub fn count_zeros(&self) -> usize {
+        self.num_bits - self.count_ones()
+    }
+
+    /// Approximate size (in bytes) of heap allocations of all containers.
+    pub fn heap_size(&self) -> usize {
+        self.chunks
+            .iter()
+            .map(|(_, container)| container.heap_size())
+            .sum()
+    }
+
+    /// Convert each container to the smallest kind (array, bitmap or runs).
+    /// Individual modifications switch between array and bitmap containers
+    /// only, and don't create run containers.
+    pub fn run_optimize(&mut self) {
+        for (_, container) in &mut self.chunks {
+            *container = container.optimized();
+        }
+    }
+
+    /// Iterate over indices of set bits, in ascending order.
+    pub fn iter_ones(&self) -> RoaringOnesIter<'_> {
+        RoaringOnesIter {
+            chunks: self.chunks.iter(),
+            current: None,
+        }
+    }
+
+    /// Panic unless `other` has the same number of bits.
+    fn check_same_len<const M: usize>(&self, other: &RoaringBoolStorage<M>) {
+        assert_eq!(
+            self.num_bits, other.num_bits,
+            "Expecting the same number of bits."
+        );
+    }
+
+    /// Intersection. `other` must have the same number of bits as `self`.
+    pub fn and<const M: usize>(&self, other: &RoaringBoolStorage<M>) -> Self {
+        self.check_same_len(other);
+        let mut chunks = Vec::new();
+        let (mut l, mut r) = (0, 0);
+        while l < self.chunks.len() && r < other.chunks.len() {
+            let (left_key, left) = &self.chunks[l];
+            let (right_key, right) = &other.chunks[r];
+            if left_key < right_key {
+                l += 1;
+            } else if left_key > right_key {
+                r += 1;
+            } else {
+                if let Some(container) = left.intersection(right) {
+                    chunks.push((*left_key, container));
+                }
+                l += 1;
+                r += 1;
+            }
+        }
+        Self {
+            chunks,
+            num_bits: self.num_bits,
+        }
+    }
+
+    /// Union. `other` must have the same number of bits as `self`.
+    pub fn or<const M: usize>(&self, other: &RoaringBoolStorage<M>) -> Self {
+        self.check_same_len(other);
+        let mut chunks = Vec::with_capacity(self.chunks.len().max(other.chunks.len()));
+        let (mut left, mut right) = (
+            self.chunks.iter().peekable(),
+            other.chunks.iter().peekable(),
+        );
+        loop {
+            let chunk = match (left.peek(), right.peek()) {
+                (Some((left_key, _)), Some((right_key, _))) if left_key < right_key => {
+                    left.next().cloned()
+                }
+                (Some((left_key, _)), Some((right_key, _))) if left_key > right_key => {
+                    right.next().cloned()
+                }
+                (Some((key, left_container)), Some((_, right_container))) => {
+                    let chunk = (*key, left_container.union(right_container));
+                    left.next();
+                    right.next();
+                    Some(chunk)
+                }
+                (Some(_), None) => left.next().cloned(),
+                (None, _) => right.next().cloned(),
+            };
+            match chunk {
+                Some(chunk) => chunks.push(chunk),
+                None => break,
+            }
+        }
+        Self {
+            chunks,
+            num_bits: self.num_bits,
+        }
+    }
+
+    /// In-place intersection.
+    pub fn and_assign<const M: usize>(&mut self, other: &RoaringBoolStorage<M>) {
+        *self = self.and(other);
+    }
+
+    /// In-place union.
+    pub fn or_assign<const M: usize>(&mut self, other: &RoaringBoolStorage<M>) {
+        *self = self.or(other);
+    }
+}
+
+impl<const N: usize, const M: usize> BitAnd<&RoaringBoolStorage<M>> for &RoaringBoolStorage<N>
+{
+    type Output = RoaringBoolStorage<N>;
+
+    fn bitand(self, rhs: &RoaringBoolStorage<M>) -> RoaringBoolStorage<N> {
+        self.and(rhs)
+    }
+}
+
+impl<const N: usize, const M: usize> BitOr<&RoaringBoolStorage<M>> for &RoaringBoolStorage<N>
+{
+    type Output = RoaringBoolStorage<N>;
+
+    fn bitor(self, rhs: &RoaringBoolStorage<M>) -> RoaringBoolStorage<N> {
+        self.or(rhs)
+    }
+}
+
+impl<const N: usize> SliceRead<bool> for RoaringBoolStorage<N> {
+    type ITER<'s>
+        = RoaringBoolIter<'s>
+    where
+        Self: 's;
+
+    fn len(&self) -> usize {
+        self.num_bits
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        match self.chunk_position(index / CHUNK_BITS) {
+            Ok(position) => self.chunks[position]
+                .1
+                .contains((index % CHUNK_BITS) as u16),
+            Err(_) => false,
+        }
+    }
+    fn iter<'s>(&'s self) -> Self::ITER<'s> {
+        RoaringBoolIter {
+            ones: self.iter_ones().peekable(),
+            index: 0,
+            num_bits: self.num_bits,
+        }
+    }
+}
+
+impl<const N: usize> SliceWrite<bool> for RoaringBoolStorage<N> {
+    fn set(&mut self, index: usize, value: &bool) {
+        self.check_and_set(index, value);
+    }
+    fn check_and_set(&mut self, index: usize, value: &bool) -> bool {
+        self.check_index(index);
+        if *value {
+            self.insert(index)
+        } else {
+            self.remove(index)
+        }
+    }
+}
+
+impl<'a, const N: usize> SliceDefault<'a, bool, N> for RoaringBoolStorage<N> {
+    type NARR = RoaringBoolStorage<0>;
+
+    // Ownership transfer constructors. They can't transfer ownership of
+    // uncompressed bools, so `from_shared` compresses a copy instead. Writes
+    // to a copy wouldn't reach `slice` or `vector`, so `from_mutable` and
+    // `from_vec_ref` panic (and their `try_*` counterparts return
+    // `Error::NotSliceBacked`).
+    fn from_shared(slice: &'a [bool]) -> Self {
+        Self::from_bits(slice.iter().cloned(), slice.len())
+    }
+    fn from_mutable(slice: &'a mut [bool]) -> Self {
+        Self::try_from_mutable(slice).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_mutable(_slice: &'a mut [bool]) -> Result<Self, Error> {
+        Err(Error::NotSliceBacked)
+    }
+    fn from_array(array: [bool; N]) -> Self {
+        Self::from_bits_to_array(array.into_iter())
+    }
+    fn from_vec(vector: Vec<bool>) -> Self {
+        let len = vector.len();
+        Self::from_bits(vector.into_iter(), len)
+    }
+    fn from_vec_ref(vector: &'a mut Vec<bool>) -> Self {
+        Self::try_from_vec_ref(vector).unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_from_vec_ref(_vector: &'a mut Vec<bool>) -> Result<Self, Error> {
+        Err(Error::NotSliceBacked)
+    }
+
+    fn from_value_to_array(value_ref: &bool) -> Self {
+        Self::new_filled(N, *value_ref)
+    }
+    fn from_value_to_vec(value: &bool, size: usize) -> Self {
+        Self::new_filled(size, *value)
+    }
+
+    fn from_iter_to_array(iter: impl Iterator<Item = bool>) -> Self {
+        Self::from_bits_to_array(iter)
+    }
+    fn from_iter_to_vec(iter: impl Iterator<Item = bool>) -> Self {
+        Self::from_bits(iter, usize::MAX)
+    }
+
+    fn from_fn_to_array(f: impl FnMut() -> bool) -> Self {
+        Self::from_bits_to_array(core::iter::repeat_with(f))
+    }
+    fn from_fn_to_vec(f: impl FnMut() -> bool, size: usize) -> Self {
+        Self::from_bits(core::iter::repeat_with(f), size)
+    }
+
+    fn from_default_to_array() -> Self {
+        Self::new(N)
+    }
+    fn from_default_to_vec(size: usize) -> Self {
+        Self::new(size)
+    }
+
+    /// There is no array to copy to. Return a copy, which must have `N` bits.
+    fn to_array_based(&self) -> Self {
+        assert!(
+            self.num_bits == N,
+            "Expecting {N} bits, but have {}.",
+            self.num_bits
+        );
+        self.clone()
+    }
+
+    fn to_vec_based(self) -> Self {
+        self
+    }
+    fn to_non_array_vec_based(&self) -> Self::NARR {
+        RoaringBoolStorage {
+            chunks: self.chunks.clone(),
+            num_bits: self.num_bits,
+        }
+    }
+
+    fn try_from_non_array_vec_based(
+        from: &Self::NARR,
+        as_choice: &SliceBackedChoice,
+    ) -> Result<Self, Error> {
+        match as_choice {
+            SliceBackedChoice::Array if from.num_bits != N => {
+                return Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: from.num_bits,
+                })
+            }
+            // Not slice-backed, so these are all alike.
+            SliceBackedChoice::Array
+            | SliceBackedChoice::Inline
+            | SliceBackedChoice::Vec
+            | SliceBackedChoice::Boxed
+            | SliceBackedChoice::Rc
+            | SliceBackedChoice::Arc => {}
+            SliceBackedChoice::Shared | SliceBackedChoice::Mutable | SliceBackedChoice::VecRef => {
+                return Err(Error::UnsupportedChoice(*as_choice))
+            }
+        }
+        Ok(Self {
+            chunks: from.chunks.clone(),
+            num_bits: from.num_bits,
+        })
+    }
+}
+
+impl<const N: usize> NewEmptyLike for RoaringBoolStorage<N> {
+    /// Keep the number of bits (there is no array/`Vec` backing to tell
+    /// apart), all clear.
+    fn new_empty_like(&self) -> Self {
+        Self::new(self.num_bits)
+    }
+}
+
+/// Iterate over indices of set bits, in ascending order.
+#[derive(Clone, Debug)]
+pub struct RoaringOnesIter<'a> {
+    chunks: core::slice::Iter<'a, (usize, Container)>,
+    /// Key and iterator of the current chunk.
+    current: Option<(usize, ContainerIter<'a>)>,
+}
+
+impl<'a> Iterator for RoaringOnesIter<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if let Some((key, iter)) = &mut self.current {
+                if let Some(low) = iter.next() {
+                    return Some(*key * CHUNK_BITS + low as usize);
+                }
+            }
+            let (key, container) = self.chunks.next()?;
+            self.current = Some((*key, container.iter()));
+        }
+    }
+}
+
+impl<'a> core::iter::FusedIterator for RoaringOnesIter<'a> {}
+
+/// Iterate over all (logical) bits.
+#[derive(Clone, Debug)]
+pub struct RoaringBoolIter<'a> {
+    ones: Peekable<RoaringOnesIter<'a>>,
+    /// Index of the next bit to return.
+    index: usize,
+    num_bits: usize,
+}
+
+impl<'a> Iterator for RoaringBoolIter<'a> {
+    type Item = &'a bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a bool> {
+        if self.index < self.num_bits {
+            let bit = self.ones.next_if_eq(&self.index).is_some();
+            self.index += 1;
+            Some(if bit { &true } else { &false })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.num_bits - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for RoaringBoolIter<'a> {}
+
+impl<'a> core::iter::FusedIterator for RoaringBoolIter<'a> {}
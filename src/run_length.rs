@@ -0,0 +1,463 @@
+use crate::error::Error;
+use crate::slices::{
+    check_empty_array_size, Slice, SliceBackedChoice, SliceDefault, SliceRead, SliceStorage,
+    SliceWrite,
+};
+use core::ops::Range;
+#[cfg(feature = "no_std_heap")]
+extern crate alloc;
+#[cfg(feature = "no_std_heap")]
+use alloc::vec::Vec;
+
+/// Run-length encoded storage. It stores each run (stretch) of equal items
+/// only once, together with where the run ends. Hence it suits items with
+/// long runs of equal values.
+///
+/// `get` is O(log(number of runs)). `set` splits and merges runs as needed,
+/// so that neighboring runs always have different values. Iterators expand
+/// runs lazily.
+///
+/// It implements `Slice` and `SliceDefault` (if `T: Default`). It has no
+/// backing storage to choose: `N` is the number of items of instances created
+/// by array-based constructors. It requires heap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunLengthStorage<T, const N: usize> {
+    /// Index right after the last item of each run. Ascending.
+    ends: Vec<usize>,
+    /// Value of each run. Neighbors differ.
+    values: Vec<T>,
+}
+
+impl<T: Copy + PartialEq, const N: usize> RunLengthStorage<T, N> {
+    /// New empty instance.
+    pub fn new() -> Self {
+        Self {
+            ends: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// New instance of `size` items, all equal to `value`.
+    fn from_value_and_size(value: T, size: usize) -> Self {
+        let mut storage = Self::new();
+        storage.push_run(value, size);
+        storage
+    }
+
+    /// Collect (up to `limit` of) `items`.
+    fn from_items(items: impl Iterator<Item = T>, limit: usize) -> Self {
+        let mut storage = Self::new();
+        for item in items.take(limit) {
+            storage.push_run(item, 1);
+        }
+        storage
+    }
+
+    /// Collect exactly `N` items. Panic if there are fewer of them (like
+    /// `SliceStorage` does).
+    fn from_items_to_array(items: impl Iterator<Item = T>) -> Self {
+        let storage = Self::from_items(items, N);
+        assert!(
+            storage.len() == N,
+            "Expecting {N} items, but got {} only.",
+            storage.len()
+        );
+        storage
+    }
+
+    /// Compress items of `storage` (of any backing).
+    pub fn from_slice_storage<const M: usize>(storage: &SliceStorage<'_, T, M>) -> Self
+    where
+        [(); check_empty_array_size(M)]:,
+    {
+        Self::from_items(storage.iter().cloned(), usize::MAX)
+    }
+
+    /// Expand into a new `SliceStorage` backed as per `as_choice`: `Array`,
+    /// `Vec`, `Boxed`, `Rc` or `Arc`. Return `Error::LengthMismatch` if `as_choice` is `Array` and
+    /// `self` doesn't have `N` items. Other choices borrow, so they return
+    /// `Error::UnsupportedChoice`.
+    pub fn to_slice_storage<'s>(
+        &self,
+        as_choice: SliceBackedChoice,
+    ) -> Result<SliceStorage<'s, T, N>, Error>
+    where
+        [(); check_empty_array_size(N)]:,
+    {
+        match as_choice {
+            SliceBackedChoice::Array if self.len() != N => Err(Error::LengthMismatch {
+                expected: N,
+                actual: self.len(),
+            }),
+            SliceBackedChoice::Array => Ok(SliceStorage::from_iter_to_array(self.iter_values())),
+            SliceBackedChoice::Vec => Ok(SliceStorage::from_iter_to_vec(self.iter_values())),
+            SliceBackedChoice::Boxed => Ok(SliceStorage::from_boxed(self.iter_values().collect())),
+            SliceBackedChoice::Rc => Ok(SliceStorage::from_rc(self.iter_values().collect())),
+            SliceBackedChoice::Arc => Ok(SliceStorage::from_arc(self.iter_values().collect())),
+            _ => Err(Error::UnsupportedChoice(as_choice)),
+        }
+    }
+
+    /// Number of (logical, expanded) items.
+    pub fn len(&self) -> usize {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    pub fn num_runs(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Iterate over runs: (range of indices, value).
+    pub fn runs(&self) -> impl Iterator<Item = (Range<usize>, &T)> + '_ {
+        self.ends
+            .iter()
+            .zip(&self.values)
+            .enumerate()
+            .map(|(run, (end, value))| (self.run_start(run)..*end, value))
+    }
+
+    /// Append `count` items equal to `value`. Extend the last run, if it has
+    /// the same value.
+    pub fn push_run(&mut self, value: T, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let end = self.len() + count;
+        if self.values.last() == Some(&value) {
+            *self.ends.last_mut().unwrap() = end;
+        } else {
+            self.ends.push(end);
+            self.values.push(value);
+        }
+    }
+
+    fn run_start(&self, run: usize) -> usize {
+        if run == 0 {
+            0
+        } else {
+            self.ends[run - 1]
+        }
+    }
+
+    /// Index of the run that contains item at `index`. Panic if `index` is
+    /// out of range.
+    fn run_of(&self, index: usize) -> usize {
+        assert!(
+            index < self.len(),
+            "Index {index} out of range for {} items.",
+            self.len()
+        );
+        self.ends.partition_point(|end| *end <= index)
+    }
+
+    fn get_value(&self, index: usize) -> T {
+        self.values[self.run_of(index)]
+    }
+
+    /// Set item at `index`, splitting and merging runs as needed. Return true
+    /// if it was different.
+    fn set_value(&mut self, index: usize, value: T) -> bool {
+        let run = self.run_of(index);
+        if self.values[run] == value {
+            return false;
+        }
+        let (start, end) = (self.run_start(run), self.ends[run]);
+        let merges_previous = index == start && run > 0 && self.values[run - 1] == value;
+        let merges_next =
+            index + 1 == end && run + 1 < self.values.len() && self.values[run + 1] == value;
+
+        match (index == start, index + 1 == end) {
+            // The whole run changes.
+            (true, true) => match (merges_previous, merges_next) {
+                (true, true) => {
+                    self.ends[run - 1] = self.ends[run + 1];
+                    self.ends.drain(run..run + 2);
+                    self.values.drain(run..run + 2);
+                }
+                (true, false) => {
+                    self.ends[run - 1] = end;
+                    self.ends.remove(run);
+                    self.values.remove(run);
+                }
+                (false, true) => {
+                    self.ends.remove(run);
+                    self.values.remove(run);
+                }
+                (false, false) => self.values[run] = value,
+            },
+            // The first item of the run changes.
+            (true, false) => {
+                if merges_previous {
+                    self.ends[run - 1] += 1;
+                } else {
+                    self.ends.insert(run, index + 1);
+                    self.values.insert(run, value);
+                }
+            }
+            // The last item of the run changes.
+            (false, true) => {
+                self.ends[run] = index;
+                if !merges_next {
+                    self.ends.insert(run + 1, end);
+                    self.values.insert(run + 1, value);
+                }
+            }
+            // An item in the middle of the run changes: split it in three.
+            (false, false) => {
+                let old_value = self.values[run];
+                self.ends[run] = index;
+                self.ends.splice(run + 1..run + 1, [index + 1, end]);
+                self.values.splice(run + 1..run + 1, [value, old_value]);
+            }
+        }
+        true
+    }
+
+    /// Iterate over items by value.
+    fn iter_values(&self) -> impl Iterator<Item = T> + '_ {
+        RunLengthIter::new(self).cloned()
+    }
+}
+
+impl<T: Copy + PartialEq, const N: usize> Default for RunLengthStorage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + PartialEq, const N: usize> SliceRead<T> for RunLengthStorage<T, N> {
+    type ITER<'i>
+        = RunLengthIter<'i, T>
+    where
+        T: 'i,
+        Self: 'i;
+
+    fn len(&self) -> usize {
+        RunLengthStorage::len(self)
+    }
+
+    fn get(&self, index: usize) -> T {
+        self.get_value(index)
+    }
+    fn iter<'i>(&'i self) -> Self::ITER<'i> {
+        RunLengthIter::new(self)
+    }
+}
+
+impl<T: Copy + PartialEq, const N: usize> SliceWrite<T> for RunLengthStorage<T, N> {
+    fn check_and_set(&mut self, index: usize, value: &T) -> bool {
+        self.set_value(index, *value)
+    }
+    fn set(&mut self, index: usize, value: &T) {
+        self.set_value(index, *value);
+    }
+}
+
+/// Parts of `Slice` and `SliceDefault` implementations that are the same.
+macro_rules! run_length_impl {
+    () => {
+        type NARR = RunLengthStorage<T, 0>;
+
+        // Ownership transfer constructors. They can't transfer ownership of
+        // expanded items, so `from_shared` compresses a copy instead. Writes
+        // to a copy wouldn't reach `slice` or `vector`, so `from_mutable` and
+        // `from_vec_ref` panic (and their `try_*` counterparts return
+        // `Error::NotSliceBacked`).
+        fn from_shared(slice: &'a [T]) -> Self {
+            Self::from_items(slice.iter().cloned(), usize::MAX)
+        }
+        fn from_mutable(slice: &'a mut [T]) -> Self {
+            Self::try_from_mutable(slice).unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_mutable(_slice: &'a mut [T]) -> Result<Self, Error> {
+            Err(Error::NotSliceBacked)
+        }
+        fn from_array(array: [T; N]) -> Self {
+            Self::from_items_to_array(array.into_iter())
+        }
+        fn from_vec(vector: Vec<T>) -> Self {
+            Self::from_items(vector.into_iter(), usize::MAX)
+        }
+        fn from_vec_ref(vector: &'a mut Vec<T>) -> Self {
+            Self::try_from_vec_ref(vector).unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_vec_ref(_vector: &'a mut Vec<T>) -> Result<Self, Error> {
+            Err(Error::NotSliceBacked)
+        }
+
+        fn from_value_to_array(value_ref: &'a T) -> Self {
+            Self::from_value_and_size(*value_ref, N)
+        }
+        fn from_value_to_vec(value: &'a T, size: usize) -> Self {
+            Self::from_value_and_size(*value, size)
+        }
+
+        fn from_iter_to_array(iter: impl Iterator<Item = T>) -> Self {
+            Self::from_items_to_array(iter)
+        }
+        fn from_iter_to_vec(iter: impl Iterator<Item = T>) -> Self {
+            Self::from_items(iter, usize::MAX)
+        }
+
+        fn from_fn_to_array(f: impl FnMut() -> T) -> Self {
+            Self::from_items_to_array(core::iter::repeat_with(f))
+        }
+        fn from_fn_to_vec(f: impl FnMut() -> T, size: usize) -> Self {
+            Self::from_items(core::iter::repeat_with(f), size)
+        }
+
+        fn to_vec_based(self) -> Self {
+            self
+        }
+        fn to_non_array_vec_based(&self) -> Self::NARR {
+            RunLengthStorage {
+                ends: self.ends.clone(),
+                values: self.values.clone(),
+            }
+        }
+        fn try_from_non_array_vec_based(
+            from: &Self::NARR,
+            as_choice: &SliceBackedChoice,
+        ) -> Result<Self, Error> {
+            match as_choice {
+                SliceBackedChoice::Array if from.len() != N => {
+                    return Err(Error::LengthMismatch {
+                        expected: N,
+                        actual: from.len(),
+                    })
+                }
+                // Not slice-backed, so these are all alike.
+                SliceBackedChoice::Array
+                | SliceBackedChoice::Inline
+                | SliceBackedChoice::Vec
+                | SliceBackedChoice::Boxed
+                | SliceBackedChoice::Rc
+                | SliceBackedChoice::Arc => {}
+                SliceBackedChoice::Shared
+                | SliceBackedChoice::Mutable
+                | SliceBackedChoice::VecRef => return Err(Error::UnsupportedChoice(*as_choice)),
+            }
+            Ok(Self {
+                ends: from.ends.clone(),
+                values: from.values.clone(),
+            })
+        }
+
+    };
+}
+
+impl<'a, T: 'a + Clone + Copy + PartialEq, const N: usize> Slice<'a, T, N>
+    for RunLengthStorage<T, N>
+where
+    [(); check_empty_array_size(N)]:,
+{
+    run_length_impl!();
+}
+
+impl<'a, T: 'a + Clone + Copy + PartialEq + Default, const N: usize> SliceDefault<'a, T, N>
+    for RunLengthStorage<T, N>
+{
+    run_length_impl!();
+
+    /// There is no array to copy to. Return a copy, which must have `N`
+    /// items.
+    fn to_array_based(&self) -> Self {
+        assert!(
+            self.len() == N,
+            "Expecting {N} items, but have {}.",
+            self.len()
+        );
+        self.clone()
+    }
+
+    fn from_default_to_array() -> Self {
+        Self::from_value_and_size(T::default(), N)
+    }
+    fn from_default_to_vec(size: usize) -> Self {
+        Self::from_value_and_size(T::default(), size)
+    }
+}
+
+/// Iterate over (expanded) items, run by run.
+#[derive(Debug, Clone)]
+pub struct RunLengthIter<'a, T> {
+    ends: &'a [usize],
+    values: &'a [T],
+    /// Index of the next item to return from the front.
+    front: usize,
+    /// Run of the next item to return from the front.
+    front_run: usize,
+    /// Index right after the next item to return from the back.
+    back: usize,
+    /// Run of the next item to return from the back.
+    back_run: usize,
+}
+
+impl<'a, T> RunLengthIter<'a, T> {
+    fn new<const N: usize>(storage: &'a RunLengthStorage<T, N>) -> Self {
+        Self {
+            ends: &storage.ends,
+            values: &storage.values,
+            front: 0,
+            front_run: 0,
+            back: storage.ends.last().copied().unwrap_or(0),
+            back_run: storage.ends.len().saturating_sub(1),
+        }
+    }
+}
+
+impl<'a, T> Iterator for RunLengthIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front < self.back {
+            while self.ends[self.front_run] <= self.front {
+                self.front_run += 1;
+            }
+            self.front += 1;
+            Some(&self.values[self.front_run])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+
+    /// Skip whole runs with a binary search.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a T> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        if self.front < self.back {
+            self.front_run += self.ends[self.front_run..].partition_point(|end| *end <= self.front);
+        }
+        self.next()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RunLengthIter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front < self.back {
+            self.back -= 1;
+            while self.back_run > 0 && self.ends[self.back_run - 1] > self.back {
+                self.back_run -= 1;
+            }
+            Some(&self.values[self.back_run])
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RunLengthIter<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for RunLengthIter<'a, T> {}
@@ -0,0 +1,320 @@
+use crate::slices::{
+    SliceBacked, SliceBackedMut, SliceRead, SliceStorage, SliceStorageDefault, SliceWrite,
+};
+use core::simd::cmp::SimdPartialEq;
+use core::simd::num::SimdUint;
+use core::simd::Simd;
+
+// Bulk operations over slices of primitives, with `core::simd` fast paths.
+// They process whole vectors (of 64 bytes) at once. Slices shorter than one
+// vector, and any remaining items, use the scalar loops of `scalar`. So do
+// whole slices on targets without vector instructions (see `HAS_SIMD`), where
+// `core::simd` would only emulate vectors.
+
+/// Number of bytes per vector. 512 bits: one AVX-512 register, or a few
+/// narrower ones.
+const VECTOR_BYTES: usize = 64;
+
+/// Whether the target has vector instructions, so that the `core::simd` fast
+/// paths pay off. Otherwise the scalar loops are selected (at compile time).
+const HAS_SIMD: bool = cfg!(any(
+    target_feature = "sse2",
+    target_feature = "neon",
+    target_feature = "simd128",
+    target_feature = "altivec",
+    target_feature = "v"
+));
+
+/// Scalar loops: for remainders shorter than a vector, and for targets
+/// without vector instructions.
+mod scalar {
+    use super::BitwiseOp;
+    use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+    pub fn fill<T: Copy>(slice: &mut [T], value: T) {
+        slice.fill(value);
+    }
+
+    pub fn eq<T: PartialEq>(left: &[T], right: &[T]) -> bool {
+        left.len() == right.len() && left.iter().zip(right).all(|(l, r)| *l == *r)
+    }
+
+    pub fn find_first<T: PartialEq>(slice: &[T], value: T) -> Option<usize> {
+        slice.iter().position(|item| *item == value)
+    }
+
+    pub fn find_first_other<T: PartialEq>(slice: &[T], value: T) -> Option<usize> {
+        slice.iter().position(|item| *item != value)
+    }
+
+    pub fn count_matching<T: PartialEq>(slice: &[T], value: T) -> usize {
+        slice.iter().filter(|item| **item == value).count()
+    }
+
+    pub fn combine_assign<T>(target: &mut [T], other: &[T], op: BitwiseOp)
+    where
+        T: Copy + BitAnd<Output = T> + BitOr<Output = T> + BitXor<Output = T> + Not<Output = T>,
+    {
+        for (t, o) in target.iter_mut().zip(other) {
+            *t = match op {
+                BitwiseOp::And => *t & *o,
+                BitwiseOp::Or => *t | *o,
+                BitwiseOp::Xor => *t ^ *o,
+                BitwiseOp::AndNot => *t & !*o,
+            };
+        }
+    }
+
+    pub fn not_assign<T: Copy + Not<Output = T>>(target: &mut [T]) {
+        for item in target {
+            *item = !*item;
+        }
+    }
+}
+
+/// Bitwise operation to combine two slices with (see
+/// `SimdBits::simd_combine_assign`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitwiseOp {
+    And,
+    Or,
+    Xor,
+    /// Clear any bits that are set in the other operand.
+    AndNot,
+}
+
+/// A primitive with `core::simd` fast paths for bulk operations.
+pub trait SimdPrimitive: Copy + PartialEq {
+    /// Set all items to `value`.
+    fn simd_fill(slice: &mut [Self], value: Self);
+    /// Whether `left` and `right` have the same items. Like `PartialEq` (so
+    /// for floats NaN doesn't equal NaN).
+    fn simd_eq(left: &[Self], right: &[Self]) -> bool;
+    /// Index of the first item equal to `value`.
+    fn simd_find_first(slice: &[Self], value: Self) -> Option<usize>;
+    /// Index of the first item not equal to `value`.
+    fn simd_find_first_other(slice: &[Self], value: Self) -> Option<usize>;
+    /// Number of items equal to `value`.
+    fn simd_count_matching(slice: &[Self], value: Self) -> usize;
+}
+
+/// An unsigned integer with `core::simd` fast paths for bulk bitwise
+/// operations.
+pub trait SimdBits: SimdPrimitive {
+    /// Total number of set bits.
+    fn simd_count_ones(slice: &[Self]) -> usize;
+    /// Combine each item of `target` with the respective item of `other`.
+    /// Both must have the same length.
+    fn simd_combine_assign(target: &mut [Self], other: &[Self], op: BitwiseOp);
+    /// Flip all bits.
+    fn simd_not_assign(target: &mut [Self]);
+}
+
+macro_rules! simd_primitive_impl {
+    ($($primitive:ty),+) => {
+        $(
+            impl SimdPrimitive for $primitive {
+                fn simd_fill(slice: &mut [Self], value: Self) {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD {
+                        return scalar::fill(slice, value);
+                    }
+                    let vector = Simd::<$primitive, LANES>::splat(value);
+                    let mut chunks = slice.chunks_exact_mut(LANES);
+                    for chunk in &mut chunks {
+                        vector.copy_to_slice(chunk);
+                    }
+                    scalar::fill(chunks.into_remainder(), value);
+                }
+
+                fn simd_eq(left: &[Self], right: &[Self]) -> bool {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD || left.len() != right.len() {
+                        return scalar::eq(left, right);
+                    }
+                    let (left_chunks, right_chunks) =
+                        (left.chunks_exact(LANES), right.chunks_exact(LANES));
+                    let (left_rest, right_rest) = (left_chunks.remainder(), right_chunks.remainder());
+                    left_chunks.zip(right_chunks).all(|(l, r)| {
+                        Simd::<$primitive, LANES>::from_slice(l)
+                            .simd_eq(Simd::from_slice(r))
+                            .all()
+                    }) && scalar::eq(left_rest, right_rest)
+                }
+
+                fn simd_find_first(slice: &[Self], value: Self) -> Option<usize> {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD {
+                        return scalar::find_first(slice, value);
+                    }
+                    let vector = Simd::<$primitive, LANES>::splat(value);
+                    let chunks = slice.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    for (chunk_index, chunk) in chunks.enumerate() {
+                        if let Some(lane) = Simd::from_slice(chunk).simd_eq(vector).first_set() {
+                            return Some(chunk_index * LANES + lane);
+                        }
+                    }
+                    scalar::find_first(rest, value)
+                        .map(|position| slice.len() - rest.len() + position)
+                }
+
+                fn simd_find_first_other(slice: &[Self], value: Self) -> Option<usize> {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD {
+                        return scalar::find_first_other(slice, value);
+                    }
+                    let vector = Simd::<$primitive, LANES>::splat(value);
+                    let chunks = slice.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    for (chunk_index, chunk) in chunks.enumerate() {
+                        if let Some(lane) = Simd::from_slice(chunk).simd_ne(vector).first_set() {
+                            return Some(chunk_index * LANES + lane);
+                        }
+                    }
+                    scalar::find_first_other(rest, value)
+                        .map(|position| slice.len() - rest.len() + position)
+                }
+
+                fn simd_count_matching(slice: &[Self], value: Self) -> usize {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD {
+                        return scalar::count_matching(slice, value);
+                    }
+                    let vector = Simd::<$primitive, LANES>::splat(value);
+                    let chunks = slice.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    chunks
+                        .map(|chunk| {
+                            Simd::from_slice(chunk).simd_eq(vector).to_bitmask().count_ones() as usize
+                        })
+                        .sum::<usize>()
+                        + scalar::count_matching(rest, value)
+                }
+            }
+        )+
+    };
+}
+simd_primitive_impl!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+macro_rules! simd_bits_impl {
+    ($($primitive:ty),+) => {
+        $(
+            impl SimdBits for $primitive {
+                fn simd_count_ones(slice: &[Self]) -> usize {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    let scalar_count_ones = |items: &[Self]| {
+                        items.iter().map(|item| item.count_ones() as usize).sum::<usize>()
+                    };
+                    if !HAS_SIMD {
+                        return scalar_count_ones(slice);
+                    }
+                    let chunks = slice.chunks_exact(LANES);
+                    let rest = chunks.remainder();
+                    chunks
+                        .map(|chunk| {
+                            Simd::<$primitive, LANES>::from_slice(chunk)
+                                .count_ones()
+                                .cast::<usize>()
+                                .reduce_sum()
+                        })
+                        .sum::<usize>()
+                        + scalar_count_ones(rest)
+                }
+
+                fn simd_combine_assign(target: &mut [Self], other: &[Self], op: BitwiseOp) {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    assert_eq!(target.len(), other.len(), "Expecting the same length.");
+                    if !HAS_SIMD {
+                        return scalar::combine_assign(target, other, op);
+                    }
+                    let mut target_chunks = target.chunks_exact_mut(LANES);
+                    let mut other_chunks = other.chunks_exact(LANES);
+                    for (t, o) in (&mut target_chunks).zip(&mut other_chunks) {
+                        let (left, right) =
+                            (Simd::<$primitive, LANES>::from_slice(t), Simd::from_slice(o));
+                        let result = match op {
+                            BitwiseOp::And => left & right,
+                            BitwiseOp::Or => left | right,
+                            BitwiseOp::Xor => left ^ right,
+                            BitwiseOp::AndNot => left & !right,
+                        };
+                        result.copy_to_slice(t);
+                    }
+                    scalar::combine_assign(target_chunks.into_remainder(), other_chunks.remainder(), op);
+                }
+
+                fn simd_not_assign(target: &mut [Self]) {
+                    const LANES: usize = VECTOR_BYTES / core::mem::size_of::<$primitive>();
+                    if !HAS_SIMD {
+                        return scalar::not_assign(target);
+                    }
+                    let mut chunks = target.chunks_exact_mut(LANES);
+                    for chunk in &mut chunks {
+                        (!Simd::<$primitive, LANES>::from_slice(chunk)).copy_to_slice(chunk);
+                    }
+                    scalar::not_assign(chunks.into_remainder());
+                }
+            }
+        )+
+    };
+}
+simd_bits_impl!(u8, u16, u32, u64, usize);
+
+/// Bulk operations for `SliceStorage` and `SliceStorageDefault` (including
+/// `crate::slices::ByteSlice`) of primitives. Modifying ones require `self`
+/// not to be Shared-based. (`crate::packed_bool::PackedBoolStorage`, and
+/// hence `crate::byte_slice::ByteSliceBoolStorage`, has its own counterparts.)
+macro_rules! slice_storage_simd_impl {
+    () => {
+        /// Set all items to `value`.
+        pub fn fill(&mut self, value: T) {
+            T::simd_fill(self.mutable_slice(), value);
+        }
+
+        /// Whether items are equal to `other`.
+        pub fn items_eq(&self, other: &[T]) -> bool {
+            T::simd_eq(self.shared_slice(), other)
+        }
+
+        /// Index of the first item equal to `value`.
+        pub fn find_first(&self, value: T) -> Option<usize> {
+            T::simd_find_first(self.shared_slice(), value)
+        }
+
+        /// Number of items equal to `value`.
+        pub fn count_matching(&self, value: T) -> usize {
+            T::simd_count_matching(self.shared_slice(), value)
+        }
+    };
+}
+
+/// Like `slice_storage_simd_impl`, but for bitwise operations.
+macro_rules! slice_storage_simd_bits_impl {
+    () => {
+        /// Total number of set bits.
+        pub fn count_ones(&self) -> usize {
+            T::simd_count_ones(self.shared_slice())
+        }
+
+        /// Combine each item with the respective item of `other`, which must
+        /// have the same length.
+        pub fn bitwise_assign(&mut self, other: &[T], op: BitwiseOp) {
+            T::simd_combine_assign(self.mutable_slice(), other, op);
+        }
+    };
+}
+
+impl<'a, T: 'a + SimdPrimitive, const N: usize> SliceStorage<'a, T, N> {
+    slice_storage_simd_impl!();
+}
+impl<'a, T: 'a + SimdBits, const N: usize> SliceStorage<'a, T, N> {
+    slice_storage_simd_bits_impl!();
+}
+
+impl<'a, T: 'a + SimdPrimitive + Default, const N: usize> SliceStorageDefault<'a, T, N> {
+    slice_storage_simd_impl!();
+}
+impl<'a, T: 'a + SimdBits + Default, const N: usize> SliceStorageDefault<'a, T, N> {
+    slice_storage_simd_bits_impl!();
+}
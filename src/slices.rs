@@ -2,8 +2,19 @@
 extern crate alloc;
 #[cfg(feature = "no_std_heap")]
 use alloc::vec::Vec;
+#[cfg(feature = "no_std_heap")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+#[cfg(not(feature = "no_std"))]
+use std::{rc::Rc, sync::Arc};
 
+use crate::array_vec::{ArrayVecIntoIter, ArrayVecStorage};
+use crate::error::{check_index, check_range, Error};
 use core::array;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::ops::{Deref, DerefMut, Index, IndexMut, RangeBounds};
+use core::slice::SliceIndex;
 
 /// This requires commas between any matching branches, even if the previous
 /// match expression (result right of `=>`) is a block `{...}`. (It can't accept
@@ -151,8 +162,6 @@ macro_rules! slice_trait {
 /// https://github.com/ranging-rs/slicing-rs/issues/1
 macro_rules! slice_trait_with_narr_size {
     ($trait_name:ident, $narr_size:expr) => {
-        type ITER<'i>: Iterator<Item = &'i T> = core::slice::Iter<'i, T> where T: 'i, Self: 'i;
-
         with_heap!{
             /// Like Self, but with size 0. `NARR` means NON_ARRAY. It serves
             /// for conversion functions that return or accept the same Slice
@@ -164,26 +173,64 @@ macro_rules! slice_trait_with_narr_size {
             type NARR: $trait_name<'a, T, $narr_size>;
         }
 
-        fn get(&self, index: usize) -> T;
-        /// Set the value. Return true if this value was not present. (Like
-        /// std::collections::HashSet.)
-        fn check_and_set(&mut self, index: usize, value: &T) -> bool;
-        /// Set the value.
-        fn set(&mut self, index: usize, value: &T);
-        fn iter<'s>(&'s self) -> Self::ITER<'s>;
-
         // Constructor that doesn't transfer an array, but it transfers its
         // slice.
         fn from_shared(slice: &'a [T]) -> Self;
+        /// Like `from_shared`. Implementations that copy `slice` (for
+        /// example, packed ones) return an error if they can't hold the copy.
+        fn try_from_shared(slice: &'a [T]) -> Result<Self, Error>
+        where
+            Self: Sized,
+        {
+            Ok(Self::from_shared(slice))
+        }
         // Constructor that doesn't transfer an array, but it transfers its
         // slice.
         fn from_mutable(slice: &'a mut [T]) -> Self;
+        /// Like `from_mutable`. Implementations that can't write through to
+        /// `slice` (for example, packed ones) return `Error::NotSliceBacked`.
+        fn try_from_mutable(slice: &'a mut [T]) -> Result<Self, Error>
+        where
+            Self: Sized,
+        {
+            Ok(Self::from_mutable(slice))
+        }
         /// Array ownership transfer constructor.
         fn from_array(array: [T; N]) -> Self;
 
         with_heap! {
             fn from_vec(vector: Vec<T>) -> Self;
         }
+        with_heap! {
+            /// Ownership transfer constructor. Implementations that aren't
+            /// backed by a slice (for example, packed ones) treat it like
+            /// `from_vec`.
+            fn from_boxed(boxed: Box<[T]>) -> Self
+            where
+                Self: Sized,
+            {
+                Self::from_vec(boxed.into_vec())
+            }
+        }
+        with_heap! {
+            /// Like `from_boxed`. Implementations that aren't backed by a
+            /// slice copy the items.
+            fn from_rc(rc: Rc<[T]>) -> Self
+            where
+                Self: Sized,
+            {
+                Self::from_vec(rc.to_vec())
+            }
+        }
+        with_heap! {
+            /// Like `from_rc`, but thread-safe.
+            fn from_arc(arc: Arc<[T]>) -> Self
+            where
+                Self: Sized,
+            {
+                Self::from_vec(arc.to_vec())
+            }
+        }
 
         with_heap! {
             fn from_vec_with_capacity(capacity: usize) -> Self
@@ -212,19 +259,43 @@ macro_rules! slice_trait_with_narr_size {
             /// alternative - for that use simple `from_shared`.
             fn from_vec_ref(vector: &'a mut Vec<T>) -> Self;
         }
+        with_heap! {
+            /// Like `from_vec_ref`. Implementations that can't write through
+            /// to `vector` return `Error::NotSliceBacked`.
+            fn try_from_vec_ref(vector: &'a mut Vec<T>) -> Result<Self, Error>
+            where
+                Self: Sized,
+            {
+                Ok(Self::from_vec_ref(vector))
+            }
+        }
 
         // Populating constructors - creating an instance that owns the data.
         fn from_value(value_ref: &'a T, size: usize, storage_type: SliceBackedChoice) -> Self
+        where
+            Self: Sized,
+        {
+            Self::try_from_value(value_ref, size, storage_type)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_value(value_ref: &'a T, size: usize, storage_type: SliceBackedChoice) -> Result<Self, Error>
         where
             Self: Sized,
         {
             match_cfg! { storage_type,
+                SliceBackedChoice::Array => Ok(Self::from_value_to_array(value_ref)),
+                SliceBackedChoice::Inline => {
+                    Self::try_from_iter_to_inline(core::iter::repeat(value_ref).take(size).cloned())
+                },
+                ~[heap~]
+                SliceBackedChoice::Vec => Ok(Self::from_value_to_vec(value_ref, size)),
+                ~[heap~]
+                SliceBackedChoice::Boxed => Ok(Self::from_boxed(core::iter::repeat(value_ref).take(size).cloned().collect())),
                 ~[heap~]
-                SliceBackedChoice::Array => Self::from_value_to_array(value_ref),
-                // @TODO test this - duplicate pattern?!
-                ~[std~]
-                SliceBackedChoice::Array | SliceBackedChoice::Array=> Self::from_value_to_array(value_ref),
-                _ => unimplemented!("Never")
+                SliceBackedChoice::Rc => Ok(Self::from_rc(core::iter::repeat(value_ref).take(size).cloned().collect())),
+                ~[heap~]
+                SliceBackedChoice::Arc => Ok(Self::from_arc(core::iter::repeat(value_ref).take(size).cloned().collect())),
+                _ => Err(Error::UnsupportedChoice(storage_type))
             }
         }
         fn from_value_to_array(value: &'a T) -> Self;
@@ -237,30 +308,108 @@ macro_rules! slice_trait_with_narr_size {
             size: usize,
             storage_type: SliceBackedChoice,
         ) -> Self
+        where
+            Self: Sized,
+        {
+            Self::try_from_iter_to(iter, size, storage_type)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_iter_to(
+            iter: impl Iterator<Item = T>,
+            size: usize,
+            storage_type: SliceBackedChoice,
+        ) -> Result<Self, Error>
         where
             Self: Sized,
         {
             match_cfg! {storage_type,
-                SliceBackedChoice::Array => Self::from_iter_to_array(iter),
+                SliceBackedChoice::Array => Self::try_from_iter_to_array(iter),
+                SliceBackedChoice::Inline => Self::try_from_iter_to_inline(iter),
+                ~[heap~]
+                SliceBackedChoice::Vec => Ok(Self::from_iter_to_vec(iter)),
+                ~[heap~]
+                SliceBackedChoice::Boxed => Ok(Self::from_boxed(iter.collect())),
+                ~[heap~]
+                SliceBackedChoice::Rc => Ok(Self::from_rc(iter.collect())),
                 ~[heap~]
-                SliceBackedChoice::Vec => Self::from_iter_to_vec(iter),
-                _ => unimplemented!("Never")
+                SliceBackedChoice::Arc => Ok(Self::from_arc(iter.collect())),
+                _ => Err(Error::UnsupportedChoice(storage_type))
             }
         }
+        /// Panic if `iter` has fewer than `N` items.
         fn from_iter_to_array(iter: impl Iterator<Item = T>) -> Self;
+        /// Like `from_iter_to_array`, but return `Error::NotEnoughItems` if
+        /// `iter` has fewer than `N` items. It buffers the items on stack
+        /// first.
+        fn try_from_iter_to_array(mut iter: impl Iterator<Item = T>) -> Result<Self, Error>
+        where
+            Self: Sized,
+        {
+            let mut actual = 0;
+            let items: [Option<T>; N] = array::from_fn(|_| {
+                let item = iter.next();
+                actual += item.is_some() as usize;
+                item
+            });
+            if actual < N {
+                return Err(Error::NotEnoughItems { expected: N, actual });
+            }
+            Ok(Self::from_iter_to_array(items.into_iter().flatten()))
+        }
         with_heap! {
             fn from_iter_to_vec(iter: impl Iterator<Item = T>) -> Self;
         }
+        /// Up to `N` items inline, then (with heap) growing into a `Vec`. See
+        /// `SliceBackedChoice::Inline`.
+        fn from_iter_to_inline(iter: impl Iterator<Item = T>) -> Self
+        where
+            Self: Sized,
+        {
+            Self::try_from_iter_to_inline(iter)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        /// Like `from_iter_to_inline`. Implementations without inline
+        /// storage (for example, packed ones) treat it like
+        /// `from_iter_to_vec`, and without heap they return
+        /// `Error::UnsupportedChoice`.
+        fn try_from_iter_to_inline(iter: impl Iterator<Item = T>) -> Result<Self, Error>
+        where
+            Self: Sized,
+        {
+            #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+            return Ok(Self::from_iter_to_vec(iter));
+            #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+            {
+                drop(iter);
+                Err(Error::UnsupportedChoice(SliceBackedChoice::Inline))
+            }
+        }
 
         fn from_fn_to(f: impl FnMut() -> T, size: usize, storage_type: SliceBackedChoice) -> Self
+        where
+            Self: Sized,
+        {
+            Self::try_from_fn_to(f, size, storage_type)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_fn_to(f: impl FnMut() -> T, size: usize, storage_type: SliceBackedChoice) -> Result<Self, Error>
         where
             Self: Sized,
         {
             match_cfg! {storage_type,
-                SliceBackedChoice::Array => Self::from_fn_to_array(f),
+                SliceBackedChoice::Array => Ok(Self::from_fn_to_array(f)),
+                SliceBackedChoice::Inline => {
+                    Self::try_from_iter_to_inline(core::iter::repeat_with(f).take(size))
+                },
+                ~[heap~]
+                SliceBackedChoice::Vec => Ok(Self::from_fn_to_vec(f, size)),
                 ~[heap~]
-                SliceBackedChoice::Vec => Self::from_fn_to_vec(f, size),
-                _ => unimplemented!("Never")
+                SliceBackedChoice::Boxed => Ok(Self::from_boxed(core::iter::repeat_with(f).take(size).collect())),
+                ~[heap~]
+                SliceBackedChoice::Rc => Ok(Self::from_rc(core::iter::repeat_with(f).take(size).collect())),
+                ~[heap~]
+                SliceBackedChoice::Arc => Ok(Self::from_arc(core::iter::repeat_with(f).take(size).collect())),
+                _ => Err(Error::UnsupportedChoice(storage_type))
             }
         }
         fn from_fn_to_array(f: impl FnMut() -> T) -> Self;
@@ -283,15 +432,24 @@ macro_rules! slice_trait_with_narr_size {
             // because `Self` is non-zero-sized. Otherwise we'd be wasting
             // memory occupied by `Self`.
             fn from_non_array_vec_based(from: &Self::NARR,
-                as_choice: &SliceBackedChoice) -> Self;
+                as_choice: &SliceBackedChoice) -> Self
+            where
+                Self: Sized,
+            {
+                Self::try_from_non_array_vec_based(from, as_choice)
+                    .unwrap_or_else(|error| panic!("{error}"))
+            }
         }
 
-        // Accessors
-        fn shared_slice(&self) -> &[T];
-        /// Implemented for all except for Shared-based slice.
-        fn mutable_slice<'s>(&'s mut self) -> &'s mut [T];
         with_heap! {
-            fn mutable_vec<'s>(&'s mut self) -> &'s mut Vec<T>;
+            /// Like `from_non_array_vec_based`. Return
+            /// `Error::LengthMismatch` if `as_choice` is `Array` and `from`
+            /// doesn't have `N` items, and `Error::UnsupportedChoice` if
+            /// `as_choice` borrows.
+            fn try_from_non_array_vec_based(from: &Self::NARR,
+                as_choice: &SliceBackedChoice) -> Result<Self, Error>
+            where
+                Self: Sized;
         }
     };
 }
@@ -301,24 +459,55 @@ macro_rules! slice_trait_default {
         /// Copy to a new array and create an instance with it.
         fn to_array_based(&self) -> Self;
 
-        /// Param `size` is used only if `storage_type ==
-        /// SliceBackedChoice::Vec`. Param `storage_type` can be only for
-        /// "owned" choices (Array/BoxArray/Vec).
+        /// Param `size` is used only if `storage_type` is not
+        /// `SliceBackedChoice::Array`. Param `storage_type` can be only for
+        /// "owned" choices (Array/Inline/Vec/Boxed/Rc/Arc).
         fn from_default(size: usize, storage_type: SliceBackedChoice) -> Self
+        where
+            Self: Sized,
+        {
+            Self::try_from_default(size, storage_type)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_from_default(size: usize, storage_type: SliceBackedChoice) -> Result<Self, Error>
         where
             Self: Sized,
         {
             match_cfg! {storage_type,
-                SliceBackedChoice::Array => Self::from_default_to_array(),
+                SliceBackedChoice::Array => Ok(Self::from_default_to_array()),
+                SliceBackedChoice::Inline => {
+                    Self::try_from_iter_to_inline(core::iter::repeat_with(T::default).take(size))
+                },
+                ~[heap~]
+                SliceBackedChoice::Vec => Ok(Self::from_default_to_vec(size)),
+                ~[heap~]
+                SliceBackedChoice::Boxed => Ok(Self::from_boxed(core::iter::repeat_with(T::default).take(size).collect())),
                 ~[heap~]
-                SliceBackedChoice::Vec => Self::from_default_to_vec(size),
-                _ => unimplemented!("Never")
+                SliceBackedChoice::Rc => Ok(Self::from_rc(core::iter::repeat_with(T::default).take(size).collect())),
+                ~[heap~]
+                SliceBackedChoice::Arc => Ok(Self::from_arc(core::iter::repeat_with(T::default).take(size).collect())),
+                _ => Err(Error::UnsupportedChoice(storage_type))
             }
         }
         fn from_default_to_array() -> Self;
         with_heap! {
             fn from_default_to_vec(size: usize) -> Self;
         }
+
+        /// Like `to_array_based`, but return `Error::LengthMismatch` unless
+        /// `self` has `N` items.
+        fn try_to_array_based(&self) -> Result<Self, Error>
+        where
+            Self: Sized,
+        {
+            if self.len() != N {
+                return Err(Error::LengthMismatch {
+                    expected: N,
+                    actual: self.len(),
+                });
+            }
+            Ok(self.to_array_based())
+        }
     };
 }
 
@@ -338,8 +527,339 @@ pub const fn check_empty_array_size(array_size: usize) -> usize {
     array_size
 }
 
+/// Read access to items. It's shared by `Slice`, `SliceClone`,
+/// `SliceDefault` and `SliceDefaultClone` (and their implementations), so
+/// read-only code can require just this, regardless of how the items are
+/// constructed or backed.
+pub trait SliceRead<T: Clone + PartialEq> {
+    /// Slice-backed implementations yield `&T`. Packed implementations that
+    /// have no `T` to refer to yield values in proxies instead.
+    type ITER<'i>: Iterator<Item: Deref<Target = T>> = core::slice::Iter<'i, T> where T: 'i, Self: 'i;
+
+    /// Number of items.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, index: usize) -> T;
+    fn try_get(&self, index: usize) -> Result<T, Error> {
+        check_index(index, self.len())?;
+        Ok(self.get(index))
+    }
+    fn iter<'s>(&'s self) -> Self::ITER<'s>;
+
+    /// Like `SliceBacked::shared_slice`. Packed or compressed
+    /// implementations have no slice of items to share, so by default this
+    /// returns `Error::NotSliceBacked`.
+    fn try_shared_slice(&self) -> Result<&[T], Error> {
+        Err(Error::NotSliceBacked)
+    }
+}
+
+/// Write access to items, on top of `SliceRead`. Code that modifies items
+/// requires this, so it can't be given a read-only implementation.
+pub trait SliceWrite<T: Clone + PartialEq>: SliceRead<T> {
+    /// Set the value. Return true if this value was not present. (Like
+    /// std::collections::HashSet.)
+    fn check_and_set(&mut self, index: usize, value: &T) -> bool;
+    /// Like `check_and_set`. Implementations that can be Shared-based
+    /// override this (and `try_set`) to return `Error::SharedNotMutable`.
+    fn try_check_and_set(&mut self, index: usize, value: &T) -> Result<bool, Error> {
+        check_index(index, self.len())?;
+        Ok(self.check_and_set(index, value))
+    }
+    /// Set the value.
+    fn set(&mut self, index: usize, value: &T);
+    fn try_set(&mut self, index: usize, value: &T) -> Result<(), Error> {
+        check_index(index, self.len())?;
+        self.set(index, value);
+        Ok(())
+    }
+
+    /// Like `SliceBackedMut::mutable_slice`. By default this returns
+    /// `Error::NotSliceBacked`. Slice-backed implementations that can be
+    /// Shared-based return `Error::SharedNotMutable` for those.
+    fn try_mutable_slice(&mut self) -> Result<&mut [T], Error> {
+        Err(Error::NotSliceBacked)
+    }
+}
+
+/// The backing slice, on top of `SliceRead`. Implemented by slice-backed
+/// implementations only, so packed or compressed ones have no
+/// `shared_slice` to call.
+pub trait SliceBacked<T: Clone + PartialEq>: SliceRead<T> {
+    fn shared_slice(&self) -> &[T];
+}
+
+/// The backing slice, mutably, on top of `SliceWrite` and `SliceBacked`.
+pub trait SliceBackedMut<T: Clone + PartialEq>: SliceWrite<T> + SliceBacked<T> {
+    /// `SliceStorage*` implement this for all their variants, so this panics
+    /// for a Shared-based instance. Use `try_mutable_slice` (or `make_mut`)
+    /// where that can happen.
+    fn mutable_slice(&mut self) -> &mut [T];
+}
+
+/// Mutable iteration over items, on top of `SliceWrite`.
+pub trait SliceIterMut<T: Clone + PartialEq>: SliceWrite<T> {
+    /// Slice-backed implementations yield `&mut T`. Packed implementations
+    /// yield proxies instead, which write the item back when dropped.
+    #[allow(non_camel_case_types)]
+    type ITER_MUT<'i>: Iterator<Item: DerefMut<Target = T>>
+    where
+        T: 'i,
+        Self: 'i;
+
+    /// Implemented for all except for Shared-based slice.
+    fn iter_mut<'s>(&'s mut self) -> Self::ITER_MUT<'s> {
+        self.try_iter_mut().unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_iter_mut<'s>(&'s mut self) -> Result<Self::ITER_MUT<'s>, Error>;
+}
+
+/// Read-only sub-slice views, on top of `SliceRead`. They borrow a part of
+/// `self`. So code can pass parts of one buffer around without dropping down
+/// to raw slices.
+pub trait SliceView<T: Clone + PartialEq>: SliceRead<T> {
+    /// Read-only, so it doesn't implement `SliceWrite`. For `SliceStorage*`
+    /// this is `SliceReadView`.
+    type VIEW<'v>: SliceRead<T>
+    where
+        T: 'v,
+        Self: 'v;
+
+    fn view<'s>(&'s self, range: impl RangeBounds<usize>) -> Self::VIEW<'s> {
+        self.try_view(range)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_view<'s>(&'s self, range: impl RangeBounds<usize>) -> Result<Self::VIEW<'s>, Error>;
+
+    /// Like `core::slice::chunks()`. Panic if `chunk_size` is 0.
+    fn chunks<'s>(
+        &'s self,
+        chunk_size: usize,
+    ) -> impl DoubleEndedIterator<Item = Self::VIEW<'s>> + ExactSizeIterator
+    where
+        T: 's;
+    /// Like `core::slice::windows()`. Panic if `size` is 0.
+    fn windows<'s>(
+        &'s self,
+        size: usize,
+    ) -> impl DoubleEndedIterator<Item = Self::VIEW<'s>> + ExactSizeIterator
+    where
+        T: 's;
+}
+
+/// Mutable sub-slice views, on top of `SliceView` and `SliceWrite`. They
+/// return new Mutable-based storages that borrow a part of `&mut self`.
+pub trait SliceViewMut<T: Clone + PartialEq>: SliceView<T> + SliceWrite<T> {
+    /// Like `SliceView::VIEW`, but writable.
+    #[allow(non_camel_case_types)]
+    type VIEW_MUT<'v>: SliceWrite<T>
+    where
+        T: 'v,
+        Self: 'v;
+
+    /// Implemented for all except for Shared-based slice.
+    fn view_mut<'s>(&'s mut self, range: impl RangeBounds<usize>) -> Self::VIEW_MUT<'s> {
+        self.try_view_mut(range)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_view_mut<'s>(
+        &'s mut self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<Self::VIEW_MUT<'s>, Error>;
+
+    /// Split into two views: `[0, mid)` and `[mid, len())`.
+    fn split_at_mut<'s>(&'s mut self, mid: usize) -> (Self::VIEW_MUT<'s>, Self::VIEW_MUT<'s>) {
+        self.try_split_at_mut(mid)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    fn try_split_at_mut<'s>(
+        &'s mut self,
+        mid: usize,
+    ) -> Result<(Self::VIEW_MUT<'s>, Self::VIEW_MUT<'s>), Error>;
+
+    /// Like `core::slice::chunks_mut()`. Panic if `chunk_size` is 0, or if
+    /// `self` is Shared-based.
+    fn chunks_mut<'s>(
+        &'s mut self,
+        chunk_size: usize,
+    ) -> impl DoubleEndedIterator<Item = Self::VIEW_MUT<'s>> + ExactSizeIterator
+    where
+        T: 's,
+    {
+        self.try_chunks_mut(chunk_size)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `chunks_mut`, but return `Error::SharedNotMutable` if `self` is
+    /// Shared-based. (Still panic if `chunk_size` is 0.)
+    fn try_chunks_mut<'s>(
+        &'s mut self,
+        chunk_size: usize,
+    ) -> Result<impl DoubleEndedIterator<Item = Self::VIEW_MUT<'s>> + ExactSizeIterator, Error>
+    where
+        T: 's;
+}
+
+/// A read-only view of (a part of) a slice, as returned by `SliceView` for
+/// `SliceStorage*`. Unlike a Shared-based `SliceStorage*`, it implements only
+/// `SliceRead` and `SliceBacked`, so a view can't be passed where
+/// `SliceWrite` is required.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SliceReadView<'v, T> {
+    slice: &'v [T],
+}
+
+impl<'v, T> SliceReadView<'v, T> {
+    pub const fn new(slice: &'v [T]) -> Self {
+        Self { slice }
+    }
+}
+
+impl<T> Clone for SliceReadView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SliceReadView<'_, T> {}
+
+impl<T: Clone + PartialEq> SliceRead<T> for SliceReadView<'_, T> {
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+    fn get(&self, index: usize) -> T {
+        self.slice[index].clone()
+    }
+    fn iter<'s>(&'s self) -> Self::ITER<'s> {
+        self.slice.iter()
+    }
+    fn try_shared_slice(&self) -> Result<&[T], Error> {
+        Ok(self.slice)
+    }
+}
+
+impl<T: Clone + PartialEq> SliceBacked<T> for SliceReadView<'_, T> {
+    fn shared_slice(&self) -> &[T] {
+        self.slice
+    }
+}
+
+/// Copy-on-write access, on top of `SliceBackedMut`. See also
+/// `crate::cow::CowStorage`.
+pub trait SliceMakeMut<T: Clone + PartialEq>: SliceBackedMut<T> {
+    /// Like `mutable_slice`, but if `self` can't be modified (for example,
+    /// if it's Shared-based), first replace it with an owned copy of its
+    /// items. That copy is Array-based if it has `N` items, otherwise
+    /// Vec-based. Rc-based and Arc-based instances stay so: they copy the
+    /// items only if they share them (see `Rc::make_mut`).
+    fn make_mut(&mut self) -> &mut [T] {
+        self.try_make_mut()
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+    /// Like `make_mut`. Without heap, return `Error::LengthMismatch` if a
+    /// copy would need other than `N` items.
+    fn try_make_mut(&mut self) -> Result<&mut [T], Error>;
+}
+
+with_heap! {
+    /// Access to the backing `Vec` of Vec-based and VecRef-based instances,
+    /// and growable operations over it. Those panic (or their `try_*`
+    /// counterparts return `Error::NotVecBased`) for fixed-size instances,
+    /// unless promoted with `promote_to_vec` first. The growable operations
+    /// delegate to their `try_*` counterparts, so implementations that grow
+    /// differently (like Inline-based `SliceStorage*`) override just those.
+    pub trait SliceVec<T: Clone + PartialEq>: SliceWrite<T> {
+        fn mutable_vec(&mut self) -> &mut Vec<T>;
+        fn try_mutable_vec(&mut self) -> Result<&mut Vec<T>, Error>;
+
+        /// Make `self` Vec-based (by copying its items), unless it's already
+        /// Vec-based or VecRef-based. Return the backing `Vec`.
+        fn promote_to_vec(&mut self) -> &mut Vec<T>;
+
+        fn push(&mut self, item: T) {
+            self.try_push(item).unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_push(&mut self, item: T) -> Result<(), Error> {
+            self.try_mutable_vec()?.push(item);
+            Ok(())
+        }
+        fn pop(&mut self) -> Option<T> {
+            self.try_pop().unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_pop(&mut self) -> Result<Option<T>, Error> {
+            Ok(self.try_mutable_vec()?.pop())
+        }
+        /// Insert at `index`, which may equal `len()`.
+        fn insert(&mut self, index: usize, item: T) {
+            self.try_insert(index, item)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_insert(&mut self, index: usize, item: T) -> Result<(), Error> {
+            let vec = self.try_mutable_vec()?;
+            if index > vec.len() {
+                return Err(Error::IndexOutOfBounds {
+                    index,
+                    len: vec.len(),
+                });
+            }
+            vec.insert(index, item);
+            Ok(())
+        }
+        fn remove(&mut self, index: usize) -> T {
+            self.try_remove(index)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_remove(&mut self, index: usize) -> Result<T, Error> {
+            let vec = self.try_mutable_vec()?;
+            check_index(index, vec.len())?;
+            Ok(vec.remove(index))
+        }
+        fn truncate(&mut self, len: usize) {
+            self.try_truncate(len).unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_truncate(&mut self, len: usize) -> Result<(), Error> {
+            self.try_mutable_vec()?.truncate(len);
+            Ok(())
+        }
+        fn resize(&mut self, new_len: usize, value: T) {
+            self.try_resize(new_len, value)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), Error> {
+            self.try_mutable_vec()?.resize(new_len, value);
+            Ok(())
+        }
+        fn extend_from_slice(&mut self, other: &[T]) {
+            self.try_extend_from_slice(other)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), Error> {
+            self.try_mutable_vec()?.extend_from_slice(other);
+            Ok(())
+        }
+        fn reserve(&mut self, additional: usize) {
+            self.try_reserve(additional)
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+            self.try_mutable_vec()?.reserve(additional);
+            Ok(())
+        }
+        fn shrink_to_fit(&mut self) {
+            self.try_shrink_to_fit()
+                .unwrap_or_else(|error| panic!("{error}"))
+        }
+        fn try_shrink_to_fit(&mut self) -> Result<(), Error> {
+            self.try_mutable_vec()?.shrink_to_fit();
+            Ok(())
+        }
+    }
+}
+
 /// Like `SliceClone`, but for `Copy` types.
-pub trait Slice<'a, T: 'a + Clone + Copy + PartialEq, const N: usize>
+pub trait Slice<'a, T: 'a + Clone + Copy + PartialEq, const N: usize>:
+    SliceRead<T>
 where
     Self: 'a,
     [(); check_empty_array_size(N)]:,
@@ -356,6 +876,14 @@ where
         self,
         storage_type: SliceBackedChoice,
     ) -> S
+    where
+        [(); check_empty_array_size(N)]:;
+    /// Like `collect_to`, but return `Error::NotEnoughItems` or
+    /// `Error::UnsupportedChoice` rather than panic.
+    fn try_collect_to<S: Slice<'a, Self::Item, N>, const N: usize>(
+        self,
+        storage_type: SliceBackedChoice,
+    ) -> Result<S, Error>
     where
         [(); check_empty_array_size(N)]:;
     fn collect_to_array<S: Slice<'a, Self::Item, N>, const N: usize>(self) -> S
@@ -376,6 +904,12 @@ where
         self,
         storage_type: SliceBackedChoice,
     ) -> S;
+    /// Like `collect_to_clone`, but return `Error::NotEnoughItems` or
+    /// `Error::UnsupportedChoice` rather than panic.
+    fn try_collect_to_clone<S: SliceClone<'a, Self::Item, N>, const N: usize>(
+        self,
+        storage_type: SliceBackedChoice,
+    ) -> Result<S, Error>;
     fn collect_to_array_clone<S: SliceClone<'a, Self::Item, N>, const N: usize>(self) -> S;
     with_heap! {
         fn collect_to_vec_clone<S: SliceClone<'a, Self::Item, N>, const N: usize>(self) -> S;
@@ -391,12 +925,16 @@ impl<'a, T: 'a + Clone + Copy + PartialEq, ITER: Iterator<Item = T>> CollectTo<'
     where
         [(); check_empty_array_size(N)]:,
     {
-        match_cfg! {storage_type,
-            SliceBackedChoice::Array => self.collect_to_array(),
-            ~[heap~]
-            SliceBackedChoice::Vec => self.collect_to_vec(),
-            _ => unimplemented!("Never")
-        }
+        S::from_iter_to(self, usize::MAX, storage_type)
+    }
+    fn try_collect_to<S: Slice<'a, Self::Item, N>, const N: usize>(
+        self,
+        storage_type: SliceBackedChoice,
+    ) -> Result<S, Error>
+    where
+        [(); check_empty_array_size(N)]:,
+    {
+        S::try_from_iter_to(self, usize::MAX, storage_type)
     }
     fn collect_to_array<S: Slice<'a, Self::Item, N>, const N: usize>(self) -> S
     where
@@ -420,12 +958,13 @@ impl<'a, T: 'a + Clone + PartialEq, ITER: Iterator<Item = T>> CollectToClone<'a>
         self,
         storage_type: SliceBackedChoice,
     ) -> S {
-        match_cfg! {storage_type,
-            SliceBackedChoice::Array => self.collect_to_array_clone(),
-            ~[heap~]
-            SliceBackedChoice::Vec => self.collect_to_vec_clone(),
-            _ => unimplemented!("Never")
-        }
+        S::from_iter_to(self, usize::MAX, storage_type)
+    }
+    fn try_collect_to_clone<S: SliceClone<'a, Self::Item, N>, const N: usize>(
+        self,
+        storage_type: SliceBackedChoice,
+    ) -> Result<S, Error> {
+        S::try_from_iter_to(self, usize::MAX, storage_type)
     }
     fn collect_to_array_clone<S: SliceClone<'a, Self::Item, N>, const N: usize>(self) -> S {
         S::from_iter_to_array(self)
@@ -437,7 +976,8 @@ impl<'a, T: 'a + Clone + PartialEq, ITER: Iterator<Item = T>> CollectToClone<'a>
     }
 }
 
-pub trait SliceDefault<'a, T: 'a + Clone + Copy + PartialEq + Default, const N: usize>
+pub trait SliceDefault<'a, T: 'a + Clone + Copy + PartialEq + Default, const N: usize>:
+    SliceRead<T>
 where
     Self: 'a,
 {
@@ -554,14 +1094,16 @@ where
 /// If `T` is `Copy`, use `Slice` instead of `SliceClone`. See also
 /// `SliceStorageClone` and `SliceStorage` for reasoning on why they are named
 /// so.
-pub trait SliceClone<'a, T: 'a + Clone + PartialEq, const N: usize>
+pub trait SliceClone<'a, T: 'a + Clone + PartialEq, const N: usize>:
+    SliceRead<T>
 where
     Self: 'a,
 {
     slice_trait!(SliceClone);
 }
 
-pub trait SliceDefaultClone<'a, T: 'a + Clone + PartialEq + Default, const N: usize>
+pub trait SliceDefaultClone<'a, T: 'a + Clone + PartialEq + Default, const N: usize>:
+    SliceRead<T>
 where
     Self: 'a,
 {
@@ -575,16 +1117,27 @@ enum_cfg! {
         /// because having a `Box` of an array would mean either wasting the
         /// unboxed array invariant's space, or introducing another const
         /// generic parameter (which would make it much less ergonomic).
-        #[derive(Debug, PartialEq)]
+        /// `Boxed` is a boxed slice instead.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum SliceBackedChoice
     ),
     Shared,
     Mutable,
     Array,
+    /// Up to `N` items inline (like `Array`, but with a runtime length),
+    /// spilling to `Vec` once it grows beyond `N`. See
+    /// `SliceStorage::new_inline`.
+    Inline,
     ~[heap~]
     Vec,
     ~[heap~]
-    VecRef
+    VecRef,
+    ~[heap~]
+    Boxed,
+    ~[heap~]
+    Rc,
+    ~[heap~]
+    Arc
 }
 
 impl SliceBackedChoice {
@@ -592,11 +1145,14 @@ impl SliceBackedChoice {
         use SliceBackedChoice::*;
         match_cfg! { self,
             Shared | Mutable => false,
-            Array => true,
+            Array | Inline => true,
             ~[heap~]
             VecRef => false,
             ~[heap~]
-            Vec => true
+            Vec | Boxed => true,
+            // Shared ownership.
+            ~[heap~]
+            Rc | Arc => true
         }
     }
 
@@ -628,12 +1184,31 @@ macro_rules! slice_storage_enum {
             /// Owned array. Suggested for stack & `no_std`.
             Array([T; N]),
 
+            /// Owned, up to `N` items inline. Growable operations of
+            /// `SliceVec` spill it to `Vec` once it needs more than `N` items.
+            /// (Without heap, it can't grow beyond `N` items.)
+            Inline(ArrayVecStorage<T, N>),
+
             /// Owned vector.
             ~[heap~]
             Vec(Vec<T>),
 
             ~[heap~]
-            VecRef(&'a mut Vec<T>)
+            VecRef(&'a mut Vec<T>),
+
+            /// Owned boxed slice. Like `Vec`, but of a fixed size, and without
+            /// spare capacity.
+            ~[heap~]
+            Boxed(Box<[T]>),
+
+            /// Shared ownership: cloning is cheap. Modifiable only while not
+            /// shared (while its reference count is 1).
+            ~[heap~]
+            Rc(Rc<[T]>),
+
+            /// Like `Rc`, but thread-safe.
+            ~[heap~]
+            Arc(Arc<[T]>)
 
             // @TODO? std => HashMap-based
         }
@@ -661,29 +1236,566 @@ fn fn_to_array<T: Clone, const N: usize>(mut f: impl FnMut() -> T) -> [T; N] {
     core::array::from_fn(|_| f())
 }
 
-macro_rules! slice_storage_impl {
-    ($enum_name:ident, $copy_or_clone_value: ident, $copy_or_clone_to_array: ident) => {
-        type ITER<'i> = core::slice::Iter<'i, T>
-            where T: 'i, Self: 'i;
+/// Implement `SliceRead`, `SliceWrite`, `SliceBacked`, `SliceBackedMut`,
+/// `SliceIterMut`, `SliceMakeMut`, `SliceView`, `SliceViewMut` and
+/// `SliceVec` for a `SliceStorage*` enum, given its item bounds.
+macro_rules! slice_storage_access_impl {
+    ($enum_name:ident, $($item_bounds:tt)+) => {
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceRead<T> for $enum_name<'a, T, N> {
+            type ITER<'i> = core::slice::Iter<'i, T>
+                where T: 'i, Self: 'i;
 
-        with_heap!{
-            type NARR = $enum_name<'a, T, 0>;
+            fn len(&self) -> usize {
+                self.shared_slice().len()
+            }
+            fn get(&self, index: usize) -> T {
+                self.shared_slice()[index].clone()
+            }
+            fn iter<'i>(&'i self) -> Self::ITER<'i> {
+                self.shared_slice().iter()
+            }
+
+            fn try_shared_slice(&self) -> Result<&[T], Error> {
+                Ok(self.shared_slice())
+            }
         }
 
-        fn get(&self, index: usize) -> T {
-            self.shared_slice()[index].clone()
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceBacked<T> for $enum_name<'a, T, N> {
+            fn shared_slice(&self) -> &[T] {
+                match_cfg! {&self,
+                    Self::Shared(slice) => slice,
+                    Self::Mutable(slice) => slice,
+                    Self::Array(array) => array,
+                    Self::Inline(inline) => inline,
+                    ~[heap~]
+                    Self::Vec(vec) => vec,
+                    ~[heap~]
+                    Self::VecRef(vec_ref) => *vec_ref,
+                    ~[heap~]
+                    Self::Boxed(boxed) => boxed,
+                    ~[heap~]
+                    Self::Rc(rc) => rc,
+                    ~[heap~]
+                    Self::Arc(arc) => arc
+                }
+            }
         }
-        fn check_and_set(&mut self, index: usize, value: &T) -> bool {
-            let mutable_slice = self.mutable_slice();
-            let is_modifying = *value != mutable_slice[index];
-            mutable_slice[index] = value.clone();
-            is_modifying
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceWrite<T> for $enum_name<'a, T, N> {
+            fn check_and_set(&mut self, index: usize, value: &T) -> bool {
+                let mutable_slice = self.mutable_slice();
+                let is_modifying = *value != mutable_slice[index];
+                mutable_slice[index] = value.clone();
+                is_modifying
+            }
+            fn try_check_and_set(&mut self, index: usize, value: &T) -> Result<bool, Error> {
+                check_index(index, self.len())?;
+                let mutable_slice = self.try_mutable_slice()?;
+                let is_modifying = *value != mutable_slice[index];
+                mutable_slice[index] = value.clone();
+                Ok(is_modifying)
+            }
+            fn set(&mut self, index: usize, value: &T) {
+                self.mutable_slice()[index] = value.clone();
+            }
+            fn try_set(&mut self, index: usize, value: &T) -> Result<(), Error> {
+                check_index(index, self.len())?;
+                self.try_mutable_slice()?[index] = value.clone();
+                Ok(())
+            }
+            fn try_mutable_slice(&mut self) -> Result<&mut [T], Error> {
+                match_cfg! {self,
+                    Self::Shared(_) => Err(Error::SharedNotMutable),
+                    Self::Mutable(slice) => Ok(slice),
+                    Self::Array(array) => Ok(array),
+                    Self::Inline(inline) => Ok(inline),
+                    ~[heap~]
+                    Self::Vec(vec) => Ok(vec),
+                    ~[heap~]
+                    Self::VecRef(vec_ref) => Ok(*vec_ref),
+                    ~[heap~]
+                    Self::Boxed(boxed) => Ok(boxed),
+                    ~[heap~]
+                    Self::Rc(rc) => Rc::get_mut(rc).ok_or(Error::SharedNotMutable),
+                    ~[heap~]
+                    Self::Arc(arc) => Arc::get_mut(arc).ok_or(Error::SharedNotMutable)
+                }
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceBackedMut<T> for $enum_name<'a, T, N> {
+            fn mutable_slice(&mut self) -> &mut [T] {
+                self.try_mutable_slice().unwrap_or_else(|error| panic!("{error}"))
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceIterMut<T> for $enum_name<'a, T, N> {
+            type ITER_MUT<'i> = core::slice::IterMut<'i, T>
+                where T: 'i, Self: 'i;
+
+            fn try_iter_mut<'s>(&'s mut self) -> Result<Self::ITER_MUT<'s>, Error> {
+                Ok(self.try_mutable_slice()?.iter_mut())
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceMakeMut<T> for $enum_name<'a, T, N> {
+            fn try_make_mut(&mut self) -> Result<&mut [T], Error> {
+                match self {
+                    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+                    Self::Rc(rc) => return Ok(Rc::make_mut(rc)),
+                    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+                    Self::Arc(arc) => return Ok(Arc::make_mut(arc)),
+                    _ => {}
+                }
+                if self.try_mutable_slice().is_err() {
+                    let slice = self.shared_slice();
+                    if slice.len() != N {
+                        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+                        return Err(Error::LengthMismatch {
+                            expected: N,
+                            actual: slice.len(),
+                        });
+                        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+                        {
+                            *self = Self::Vec(slice.to_vec());
+                            return self.try_mutable_slice();
+                        }
+                    }
+                    *self = Self::Array(array::from_fn(|i| slice[i].clone()));
+                }
+                self.try_mutable_slice()
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceView<T> for $enum_name<'a, T, N> {
+            type VIEW<'v> = SliceReadView<'v, T>
+                where T: 'v, Self: 'v;
+
+            fn try_view<'s>(&'s self, range: impl RangeBounds<usize>) -> Result<Self::VIEW<'s>, Error> {
+                let range = check_range(range, self.len())?;
+                Ok(SliceReadView::new(&self.shared_slice()[range]))
+            }
+
+            fn chunks<'s>(
+                &'s self,
+                chunk_size: usize,
+            ) -> impl DoubleEndedIterator<Item = Self::VIEW<'s>> + ExactSizeIterator
+            where
+                T: 's,
+            {
+                self.shared_slice().chunks(chunk_size).map(SliceReadView::new)
+            }
+            fn windows<'s>(
+                &'s self,
+                size: usize,
+            ) -> impl DoubleEndedIterator<Item = Self::VIEW<'s>> + ExactSizeIterator
+            where
+                T: 's,
+            {
+                self.shared_slice().windows(size).map(SliceReadView::new)
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceViewMut<T> for $enum_name<'a, T, N> {
+            type VIEW_MUT<'v> = $enum_name<'v, T, 0>
+                where T: 'v, Self: 'v;
+
+            fn try_view_mut<'s>(
+                &'s mut self,
+                range: impl RangeBounds<usize>,
+            ) -> Result<Self::VIEW_MUT<'s>, Error> {
+                let range = check_range(range, self.len())?;
+                Ok($enum_name::Mutable(&mut self.try_mutable_slice()?[range]))
+            }
+
+            fn try_split_at_mut<'s>(
+                &'s mut self,
+                mid: usize,
+            ) -> Result<(Self::VIEW_MUT<'s>, Self::VIEW_MUT<'s>), Error> {
+                let range = check_range(..mid, self.len())?;
+                let (left, right) = self.try_mutable_slice()?.split_at_mut(range.end);
+                Ok(($enum_name::Mutable(left), $enum_name::Mutable(right)))
+            }
+
+            fn try_chunks_mut<'s>(
+                &'s mut self,
+                chunk_size: usize,
+            ) -> Result<impl DoubleEndedIterator<Item = Self::VIEW_MUT<'s>> + ExactSizeIterator, Error>
+            where
+                T: 's,
+            {
+                Ok(self.try_mutable_slice()?.chunks_mut(chunk_size).map($enum_name::Mutable))
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> $enum_name<'a, T, N> {
+            /// New empty Inline-based instance.
+            pub const fn new_inline() -> Self {
+                Self::Inline(ArrayVecStorage::new())
+            }
+
+            /// New instance with a copy of `slice`. It's Inline-based if
+            /// `slice` fits, otherwise Vec-based. Without heap, panic if it
+            /// doesn't fit.
+            pub fn from_slice_to_inline(slice: &[T]) -> Self {
+                Self::try_from_slice_to_inline(slice)
+                    .unwrap_or_else(|error| panic!("{error}"))
+            }
+            /// Like `from_slice_to_inline`. Without heap, return
+            /// `Error::CapacityExceeded` if `slice` doesn't fit.
+            pub fn try_from_slice_to_inline(slice: &[T]) -> Result<Self, Error> {
+                match ArrayVecStorage::try_from_slice(slice) {
+                    Ok(inline) => Ok(Self::Inline(inline)),
+                    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+                    Err(_) => Ok(Self::Vec(slice.to_vec())),
+                    #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+                    Err(error) => Err(error),
+                }
+            }
+
+            /// Which variant `self` is. So, for example, `Inline` until an
+            /// Inline-based instance spills, and `Vec` after that.
+            pub fn backed_choice(&self) -> SliceBackedChoice {
+                match_cfg! {self,
+                    Self::Shared(_) => SliceBackedChoice::Shared,
+                    Self::Mutable(_) => SliceBackedChoice::Mutable,
+                    Self::Array(_) => SliceBackedChoice::Array,
+                    Self::Inline(_) => SliceBackedChoice::Inline,
+                    ~[heap~]
+                    Self::Vec(_) => SliceBackedChoice::Vec,
+                    ~[heap~]
+                    Self::VecRef(_) => SliceBackedChoice::VecRef,
+                    ~[heap~]
+                    Self::Boxed(_) => SliceBackedChoice::Boxed,
+                    ~[heap~]
+                    Self::Rc(_) => SliceBackedChoice::Rc,
+                    ~[heap~]
+                    Self::Arc(_) => SliceBackedChoice::Arc
+                }
+            }
+
+            with_heap! {
+                /// Spill to `Vec` if Inline-based and `additional` more items
+                /// wouldn't fit inline.
+                fn spill_for(&mut self, additional: usize) {
+                    if let Self::Inline(inline) = self {
+                        if additional > N - inline.len() {
+                            let mut vec = Vec::with_capacity(inline.len() + additional);
+                            vec.extend(core::mem::take(inline));
+                            *self = Self::Vec(vec);
+                        }
+                    }
+                }
+            }
+        }
+
+        with_heap! {
+            /// Inline-based instances grow in place while they fit, and spill
+            /// to `Vec` when they don't. Once Vec-based, they stay so.
+            impl<'a, T: 'a + $($item_bounds)+, const N: usize> SliceVec<T> for $enum_name<'a, T, N> {
+                fn mutable_vec(&mut self) -> &mut Vec<T> {
+                    self.try_mutable_vec().unwrap_or_else(|error| panic!("{error}"))
+                }
+                /// Return `Error::NotVecBased` while Inline-based, too. (See
+                /// `promote_to_vec`.)
+                fn try_mutable_vec(&mut self) -> Result<&mut Vec<T>, Error> {
+                    match self {
+                        Self::Vec(vec) => Ok(vec),
+                        Self::VecRef(vec_ref) => Ok(*vec_ref),
+                        _ => Err(Error::NotVecBased),
+                    }
+                }
+                fn promote_to_vec(&mut self) -> &mut Vec<T> {
+                    if !matches!(self, Self::Vec(_) | Self::VecRef(_)) {
+                        *self = Self::Vec(self.shared_slice().to_vec());
+                    }
+                    self.mutable_vec()
+                }
+
+                fn try_push(&mut self, item: T) -> Result<(), Error> {
+                    self.spill_for(1);
+                    match self {
+                        Self::Inline(inline) => inline.try_push(item),
+                        _ => {
+                            self.try_mutable_vec()?.push(item);
+                            Ok(())
+                        }
+                    }
+                }
+                fn try_pop(&mut self) -> Result<Option<T>, Error> {
+                    match self {
+                        Self::Inline(inline) => Ok(inline.pop()),
+                        _ => Ok(self.try_mutable_vec()?.pop()),
+                    }
+                }
+                fn try_insert(&mut self, index: usize, item: T) -> Result<(), Error> {
+                    // Check before spilling.
+                    let len = self.len();
+                    if index > len {
+                        return Err(Error::IndexOutOfBounds { index, len });
+                    }
+                    self.spill_for(1);
+                    match self {
+                        Self::Inline(inline) => inline.try_insert(index, item),
+                        _ => {
+                            self.try_mutable_vec()?.insert(index, item);
+                            Ok(())
+                        }
+                    }
+                }
+                fn try_remove(&mut self, index: usize) -> Result<T, Error> {
+                    match self {
+                        Self::Inline(inline) => inline.try_remove(index),
+                        _ => {
+                            let vec = self.try_mutable_vec()?;
+                            check_index(index, vec.len())?;
+                            Ok(vec.remove(index))
+                        }
+                    }
+                }
+                fn try_truncate(&mut self, len: usize) -> Result<(), Error> {
+                    match self {
+                        Self::Inline(inline) => inline.truncate(len),
+                        _ => self.try_mutable_vec()?.truncate(len),
+                    }
+                    Ok(())
+                }
+                fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), Error> {
+                    self.spill_for(new_len.saturating_sub(self.len()));
+                    match self {
+                        Self::Inline(inline) => inline.try_resize(new_len, value),
+                        _ => {
+                            self.try_mutable_vec()?.resize(new_len, value);
+                            Ok(())
+                        }
+                    }
+                }
+                fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), Error> {
+                    self.spill_for(other.len());
+                    match self {
+                        Self::Inline(inline) => inline.try_extend_from_slice(other),
+                        _ => {
+                            self.try_mutable_vec()?.extend_from_slice(other);
+                            Ok(())
+                        }
+                    }
+                }
+                fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+                    self.spill_for(additional);
+                    match self {
+                        Self::Inline(_) => Ok(()),
+                        _ => {
+                            self.try_mutable_vec()?.reserve(additional);
+                            Ok(())
+                        }
+                    }
+                }
+                fn try_shrink_to_fit(&mut self) -> Result<(), Error> {
+                    match self {
+                        Self::Inline(_) => Ok(()),
+                        _ => {
+                            self.try_mutable_vec()?.shrink_to_fit();
+                            Ok(())
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+slice_storage_access_impl!(SliceStorage, Clone + Copy + PartialEq);
+slice_storage_access_impl!(SliceStorageClone, Clone + PartialEq);
+slice_storage_access_impl!(SliceStorageDefault, Clone + Copy + PartialEq + Default);
+slice_storage_access_impl!(SliceStorageDefaultClone, Clone + PartialEq + Default);
+
+/// Implement standard traits for a `SliceStorage*` enum, given its item
+/// bounds. They work with the items regardless of the variant. So, for
+/// example, an Array-based instance and a Vec-based instance with the same
+/// items are equal, and they have the same hash. Mutable access (`AsMut`,
+/// `IndexMut` and iterating over `&mut`) panics for a Shared-based instance,
+/// like `mutable_slice` does. Use `try_mutable_slice`, `try_set` or
+/// `try_iter_mut` to get `Error::SharedNotMutable` instead.
+macro_rules! slice_storage_std_impl {
+    ($enum_name:ident, $($item_bounds:tt)+) => {
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> Deref for $enum_name<'a, T, N> {
+            type Target = [T];
+            fn deref(&self) -> &[T] {
+                self.shared_slice()
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> AsRef<[T]> for $enum_name<'a, T, N> {
+            fn as_ref(&self) -> &[T] {
+                self.shared_slice()
+            }
+        }
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> AsMut<[T]> for $enum_name<'a, T, N> {
+            fn as_mut(&mut self) -> &mut [T] {
+                self.mutable_slice()
+            }
+        }
+
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize, I: SliceIndex<[T]>> Index<I>
+            for $enum_name<'a, T, N>
+        {
+            type Output = I::Output;
+            fn index(&self, index: I) -> &I::Output {
+                &self.shared_slice()[index]
+            }
+        }
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize, I: SliceIndex<[T]>> IndexMut<I>
+            for $enum_name<'a, T, N>
+        {
+            fn index_mut(&mut self, index: I) -> &mut I::Output {
+                &mut self.mutable_slice()[index]
+            }
+        }
+
+        impl<'s, 'a, T: 'a + $($item_bounds)+, const N: usize> IntoIterator
+            for &'s $enum_name<'a, T, N>
+        {
+            type Item = &'s T;
+            type IntoIter = core::slice::Iter<'s, T>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.shared_slice().iter()
+            }
+        }
+        impl<'s, 'a, T: 'a + $($item_bounds)+, const N: usize> IntoIterator
+            for &'s mut $enum_name<'a, T, N>
+        {
+            type Item = &'s mut T;
+            type IntoIter = core::slice::IterMut<'s, T>;
+            fn into_iter(self) -> Self::IntoIter {
+                self.mutable_slice().iter_mut()
+            }
         }
-        fn set(&mut self, index: usize, value: &T) {
-            self.mutable_slice()[index] = value.clone();
+        impl<'a, T: 'a + $($item_bounds)+, const N: usize> IntoIterator for $enum_name<'a, T, N> {
+            type Item = T;
+            type IntoIter = SliceStorageIntoIter<'a, T, N>;
+            fn into_iter(self) -> Self::IntoIter {
+                match_cfg! {self,
+                    Self::Shared(slice) => SliceStorageIntoIter::Borrowed(slice.iter().cloned()),
+                    Self::Mutable(slice) => {
+                        let slice: &'a [T] = slice;
+                        SliceStorageIntoIter::Borrowed(slice.iter().cloned())
+                    },
+                    Self::Array(array) => SliceStorageIntoIter::Array(array.into_iter()),
+                    Self::Inline(inline) => SliceStorageIntoIter::Inline(inline.into_iter()),
+                    ~[heap~]
+                    Self::Vec(vec) => SliceStorageIntoIter::Vec(vec.into_iter()),
+                    ~[heap~]
+                    Self::VecRef(vec_ref) => {
+                        let vec_ref: &'a Vec<T> = vec_ref;
+                        SliceStorageIntoIter::Borrowed(vec_ref.iter().cloned())
+                    },
+                    ~[heap~]
+                    Self::Boxed(boxed) => SliceStorageIntoIter::Vec(boxed.into_vec().into_iter()),
+                    ~[heap~]
+                    Self::Rc(rc) => SliceStorageIntoIter::Vec(rc.to_vec().into_iter()),
+                    ~[heap~]
+                    Self::Arc(arc) => SliceStorageIntoIter::Vec(arc.to_vec().into_iter())
+                }
+            }
+        }
+
+        impl<'a, 'b, T: 'a + 'b + $($item_bounds)+, const N: usize, const M: usize>
+            PartialEq<$enum_name<'b, T, M>> for $enum_name<'a, T, N>
+        {
+            fn eq(&self, other: &$enum_name<'b, T, M>) -> bool {
+                self.shared_slice() == other.shared_slice()
+            }
+        }
+        impl<'a, T: 'a + Eq + $($item_bounds)+, const N: usize> Eq for $enum_name<'a, T, N> {}
+
+        impl<'a, T: 'a + Hash + $($item_bounds)+, const N: usize> Hash for $enum_name<'a, T, N> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.shared_slice().hash(state);
+            }
+        }
+
+        impl<'a, 'b, T: 'a + 'b + PartialOrd + $($item_bounds)+, const N: usize, const M: usize>
+            PartialOrd<$enum_name<'b, T, M>> for $enum_name<'a, T, N>
+        {
+            fn partial_cmp(&self, other: &$enum_name<'b, T, M>) -> Option<Ordering> {
+                self.shared_slice().partial_cmp(other.shared_slice())
+            }
         }
-        fn iter<'i>(&'i self) -> Self::ITER<'i> {
-            self.shared_slice().iter()
+        impl<'a, T: 'a + Ord + $($item_bounds)+, const N: usize> Ord for $enum_name<'a, T, N> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.shared_slice().cmp(other.shared_slice())
+            }
+        }
+    };
+}
+
+slice_storage_std_impl!(SliceStorage, Clone + Copy + PartialEq);
+slice_storage_std_impl!(SliceStorageClone, Clone + PartialEq);
+slice_storage_std_impl!(SliceStorageDefault, Clone + Copy + PartialEq + Default);
+slice_storage_std_impl!(SliceStorageDefaultClone, Clone + PartialEq + Default);
+
+enum_cfg! {
+    (
+        /// Owning iterator over items of any `SliceStorage*` (see their
+        /// `IntoIterator` implementations). It moves items out of Array-based
+        /// and Vec-based instances, and it clones them out of the borrowing
+        /// ones.
+        #[derive(Debug, Clone)]
+        pub enum SliceStorageIntoIter<'a, T: 'a, const N: usize>
+    ),
+    Borrowed(core::iter::Cloned<core::slice::Iter<'a, T>>),
+    Array(core::array::IntoIter<T, N>),
+    Inline(ArrayVecIntoIter<T, N>),
+    ~[heap~]
+    Vec(<Vec<T> as IntoIterator>::IntoIter)
+}
+
+impl<'a, T: 'a + Clone, const N: usize> Iterator for SliceStorageIntoIter<'a, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        match_cfg! {self,
+            Self::Borrowed(iter) => iter.next(),
+            Self::Array(iter) => iter.next(),
+            Self::Inline(iter) => iter.next(),
+            ~[heap~]
+            Self::Vec(iter) => iter.next()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match_cfg! {self,
+            Self::Borrowed(iter) => iter.size_hint(),
+            Self::Array(iter) => iter.size_hint(),
+            Self::Inline(iter) => iter.size_hint(),
+            ~[heap~]
+            Self::Vec(iter) => iter.size_hint()
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone, const N: usize> DoubleEndedIterator for SliceStorageIntoIter<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        match_cfg! {self,
+            Self::Borrowed(iter) => iter.next_back(),
+            Self::Array(iter) => iter.next_back(),
+            Self::Inline(iter) => iter.next_back(),
+            ~[heap~]
+            Self::Vec(iter) => iter.next_back()
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone, const N: usize> ExactSizeIterator for SliceStorageIntoIter<'a, T, N> {}
+impl<'a, T: 'a + Clone, const N: usize> FusedIterator for SliceStorageIntoIter<'a, T, N> {}
+
+macro_rules! slice_storage_impl {
+    ($enum_name:ident, $copy_or_clone_value: ident, $copy_or_clone_to_array: ident) => {
+        with_heap!{
+            type NARR = $enum_name<'a, T, 0>;
         }
 
         // Ownership transfer constructors.
@@ -715,6 +1827,27 @@ macro_rules! slice_storage_impl {
                 Self::Vec(vector)
             }
         }
+        with_heap! {
+            fn from_boxed(boxed: Box<[T]>) -> Self {
+                #[cfg(feature = "size_for_array_only")]
+                assert_eq!(N, 0);
+                Self::Boxed(boxed)
+            }
+        }
+        with_heap! {
+            fn from_rc(rc: Rc<[T]>) -> Self {
+                #[cfg(feature = "size_for_array_only")]
+                assert_eq!(N, 0);
+                Self::Rc(rc)
+            }
+        }
+        with_heap! {
+            fn from_arc(arc: Arc<[T]>) -> Self {
+                #[cfg(feature = "size_for_array_only")]
+                assert_eq!(N, 0);
+                Self::Arc(arc)
+            }
+        }
         with_heap! {
             fn from_vec_ref(vector: &'a mut Vec<T>) -> Self {
                 Self::VecRef(vector)
@@ -741,6 +1874,29 @@ macro_rules! slice_storage_impl {
                 Self::Vec(iter.collect::<Vec<_>>())
             }
         }
+        /// Without heap, return `Error::CapacityExceeded` if `iter` has more
+        /// than `N` items.
+        fn try_from_iter_to_inline(mut iter: impl Iterator<Item = T>) -> Result<Self, Error> {
+            let mut inline = ArrayVecStorage::new();
+            while !inline.is_full() {
+                match iter.next() {
+                    Some(item) => inline.push(item),
+                    None => return Ok(Self::Inline(inline)),
+                }
+            }
+            match iter.next() {
+                None => Ok(Self::Inline(inline)),
+                #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))] // with_heap
+                Some(item) => {
+                    let mut vec = Vec::from_iter(inline);
+                    vec.push(item);
+                    vec.extend(iter);
+                    Ok(Self::Vec(vec))
+                }
+                #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+                Some(_) => Err(Error::CapacityExceeded { capacity: N }),
+            }
+        }
 
         fn from_fn_to_array(f: impl FnMut() -> T) -> Self {
             Self::Array(fn_to_array(f))
@@ -761,8 +1917,12 @@ macro_rules! slice_storage_impl {
                     Self::Mutable(mutable) => Self::Vec(mutable.iter().cloned().collect::<Vec<_>>()),
                     // @TODO Vec::with_capacity --\\
                     Self::Array(arr) => Self::Vec(arr.iter().cloned().collect::<Vec<_>>()),
+                    Self::Inline(inline) => Self::Vec(inline.into_iter().collect::<Vec<_>>()),
                     Self::Vec(_) => self,
                     Self::VecRef(_) => self,
+                    Self::Boxed(boxed) => Self::Vec(boxed.into_vec()),
+                    Self::Rc(rc) => Self::Vec(rc.to_vec()),
+                    Self::Arc(arc) => Self::Vec(arc.to_vec()),
                 }
             }
         }
@@ -775,9 +1935,13 @@ macro_rules! slice_storage_impl {
                 } else {
                     let slice = match self {
                         Self::Array(array) => array,
+                        Self::Inline(inline) => &inline[..],
                         Self::Shared(shared_slice) => *shared_slice,
                         Self::Vec(vec) => vec,
                         Self::VecRef(vec_ref) => *vec_ref,
+                        Self::Boxed(boxed) => boxed,
+                        Self::Rc(rc) => rc,
+                        Self::Arc(arc) => arc,
                         Self::Mutable(_) => unreachable!(),
                     };
                     v = Vec::from_iter(slice.iter().cloned());
@@ -787,63 +1951,26 @@ macro_rules! slice_storage_impl {
         }
 
         with_heap! {
-            fn from_non_array_vec_based(from: &Self::NARR,
+            fn try_from_non_array_vec_based(from: &Self::NARR,
                 as_choice: &SliceBackedChoice
-            ) -> Self {
+            ) -> Result<Self, Error> {
                 use SliceBackedChoice::*;
-                let from_vec;
-                match from {
-                    Self::NARR::Vec(vec) => from_vec = vec,
-                    _ => unreachable!("NARR must always be Vec-based!")
-                }
+                let Self::NARR::Vec(from_vec) = from else {
+                    return Err(Error::NotVecBased);
+                };
                 match as_choice {
-                    Shared | Mutable => unimplemented!("Never"),
-                    Array => {
-                        Self::Array(array::from_fn(|i| from_vec[i].clone()))
-                    },
-                    Vec => {
-                        Self::from_vec(from_vec.clone())
-                    },
-                    VecRef => unimplemented!("Never"),
-                }
-            }
-        }
-
-        // Accessors
-        fn shared_slice(&self) -> &[T] {
-            match_cfg! {&self,
-                Self::Shared(slice) => slice,
-                Self::Mutable(slice) => slice,
-                Self::Array(array) => array,
-                ~[heap~]
-                Self::Vec(vec) => vec,
-                ~[heap~]
-                Self::VecRef(vec_ref) => *vec_ref
-            }
-        }
-
-        fn mutable_slice<'s>(&'s mut self) -> &'s mut [T] {
-            match_cfg! {self,
-                Self::Shared(_) => {
-                    unimplemented!("Can't get a mutable slice from a shared slice.")
-                },
-                Self::Mutable(slice) => slice,
-                Self::Array(array) => array,
-                ~[heap~]
-                Self::Vec(vec) => vec,
-                ~[heap~]
-                Self::VecRef(vec_ref) => *vec_ref
-            }
-        }
-
-        with_heap! {
-            fn mutable_vec<'s>(&'s mut self) -> &'s mut Vec<T> {
-                match self {
-                    Self::Vec(vec) => vec,
-                    Self::VecRef(vec_ref) => *vec_ref,
-                    _ => {
-                        unimplemented!("Works for Vec and VecRef only.")
-                    }
+                    Array if from_vec.len() != N => Err(Error::LengthMismatch {
+                        expected: N,
+                        actual: from_vec.len(),
+                    }),
+                    Array => Ok(Self::Array(array::from_fn(|i| from_vec[i].clone()))),
+                    // Inline-based if it fits, otherwise Vec-based.
+                    Inline => Self::try_from_slice_to_inline(from_vec),
+                    Vec => Ok(Self::from_vec(from_vec.clone())),
+                    Boxed => Ok(Self::from_boxed(from_vec.as_slice().into())),
+                    Rc => Ok(Self::from_rc(from_vec.as_slice().into())),
+                    Arc => Ok(Self::from_arc(from_vec.as_slice().into())),
+                    Shared | Mutable | VecRef => Err(Error::UnsupportedChoice(*as_choice)),
                 }
             }
         }
@@ -902,6 +2029,11 @@ macro_rules! slice_storage_default_impl {
                     to.$copy_or_clone_from_slice(*slice);
                     Self::Array(to)
                 },
+                Self::Inline(inline) => {
+                    let mut to = $copy_or_clone_default();
+                    to.$copy_or_clone_from_slice(inline);
+                    Self::Array(to)
+                },
                 ~[heap~]
                 Self::Vec(vec) => {
                     let mut to = $copy_or_clone_default();
@@ -913,6 +2045,12 @@ macro_rules! slice_storage_default_impl {
                     let mut to = $copy_or_clone_default();
                     to.$copy_or_clone_from_slice(*vec_ref);
                     Self::Array(to)
+                },
+                ~[heap~]
+                Self::Boxed(_) | Self::Rc(_) | Self::Arc(_) => {
+                    let mut to = $copy_or_clone_default();
+                    to.$copy_or_clone_from_slice(self.shared_slice());
+                    Self::Array(to)
                 }
             }
         }
@@ -947,23 +2085,30 @@ impl<'a, T: 'a + Clone + PartialEq + Default, const N: usize> SliceDefaultClone<
 
 macro_rules! slice_storage_impl_clone {
     () => {
-        /// Implemented for Array-backed and Vec-backed SliceStorage only. For
-        /// Vec (mutable) reference-backed SliceStorage this creates a new,
-        /// owned Vec-based instance.
+        /// Implemented for all but (mutable) slice-backed SliceStorage. A
+        /// shared slice-backed instance borrows the same slice again. For Vec
+        /// (mutable) reference-backed SliceStorage this creates a new, owned
+        /// Vec-based instance. Rc-backed and Arc-backed instances are cheap
+        /// to clone: they share the items.
         fn clone(&self) -> Self {
             match_cfg! {self,
-                Self::Shared(_) => {
-                    unimplemented!("Don't clone a shared slice-backed SliceStorage. Instead, pass a shared reference to it.")
-                },
+                Self::Shared(slice) => Self::Shared(slice),
                 Self::Mutable(_) => {
                     unimplemented!("Can't clone a mutable slice.")
                 },
                 Self::Array(array) => Self::Array(array.clone()),
+                Self::Inline(inline) => Self::Inline(inline.clone()),
                 ~[heap~]
                 Self::Vec(vec) => Self::Vec(vec.clone()),
                 // Can't clone a mutable reference. Clone the vector itself.
                 ~[heap~]
-                Self::VecRef(vec_ref) => Self::Vec((*vec_ref).clone())
+                Self::VecRef(vec_ref) => Self::Vec((*vec_ref).clone()),
+                ~[heap~]
+                Self::Boxed(boxed) => Self::Boxed(boxed.clone()),
+                ~[heap~]
+                Self::Rc(rc) => Self::Rc(Rc::clone(rc)),
+                ~[heap~]
+                Self::Arc(arc) => Self::Arc(Arc::clone(arc))
             }
         }
     }
@@ -988,8 +2133,9 @@ impl<'a, T: 'a + Clone + PartialEq + Default, const N: usize> Clone
 
 macro_rules! slice_storage_newlike_impl {
     ($copy_or_clone_default: ident) => {
-        /// Implemented for Shared-backed, Array-backed and Vec-backed (but not
-        /// VecRef-backed) variants only.
+        /// Implemented for Shared-backed, Array-backed, Inline-backed,
+        /// Vec-backed and Boxed/Rc/Arc-backed (but not VecRef-backed) variants
+        /// only. An Inline-backed one becomes empty.
         fn new_empty_like(&self) -> Self {
             match_cfg! {self,
                 Self::Shared(slice) => Self::Shared(slice),
@@ -997,12 +2143,20 @@ macro_rules! slice_storage_newlike_impl {
                     unimplemented!("Can't clone a mutable slice.")
                 },
                 Self::Array(_) => Self::Array($copy_or_clone_default()),
+                Self::Inline(_) => Self::Inline(ArrayVecStorage::new()),
                 ~[heap~]
                 Self::Vec(vec) => Self::Vec(Vec::with_capacity(vec.len())),
                 ~[heap~]
                 Self::VecRef(_) => {
                     unimplemented!("Can't clone a mutable Vec reference.")
-                }
+                },
+                // Like arrays, these keep their size.
+                ~[heap~]
+                Self::Boxed(boxed) => Self::Boxed(boxed.iter().map(|_| T::default()).collect()),
+                ~[heap~]
+                Self::Rc(rc) => Self::Rc(rc.iter().map(|_| T::default()).collect()),
+                ~[heap~]
+                Self::Arc(arc) => Self::Arc(arc.iter().map(|_| T::default()).collect())
             }
         }
     };
@@ -0,0 +1,123 @@
+/// Helpers for `ArrayVecStorage`. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod array_vec {
+    use core::cell::Cell;
+    use slicing::array_vec::ArrayVecStorage;
+    use slicing::slices::{SliceBacked, SliceIterMut, SliceRead, SliceWrite};
+    use slicing::Error;
+
+    pub fn push_pop_insert_remove() {
+        let mut storage = ArrayVecStorage::<u8, 4>::new();
+        assert!(storage.is_empty());
+        assert_eq!(storage.capacity(), 4);
+        storage.push(1);
+        storage.push(2);
+        storage.insert(0, 0);
+        storage.insert(3, 3);
+        assert!(storage.is_full());
+        assert_eq!(storage.shared_slice(), &[0, 1, 2, 3]);
+        assert_eq!(
+            storage.try_push(4),
+            Err(Error::CapacityExceeded { capacity: 4 })
+        );
+        assert_eq!(
+            storage.try_insert(0, 4),
+            Err(Error::CapacityExceeded { capacity: 4 })
+        );
+
+        assert_eq!(storage.remove(1), 1);
+        assert_eq!(storage.pop(), Some(3));
+        assert_eq!(storage.shared_slice(), &[0, 2]);
+        assert_eq!(
+            storage.try_remove(2),
+            Err(Error::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(
+            storage.try_insert(3, 9),
+            Err(Error::IndexOutOfBounds { index: 3, len: 2 })
+        );
+        storage.clear();
+        assert_eq!(storage.pop(), None);
+    }
+
+    pub fn resize_and_extend() {
+        let mut storage = ArrayVecStorage::<u16, 5>::from_slice(&[1, 2]);
+        storage.extend_from_slice(&[3]);
+        storage.resize(5, 7);
+        assert_eq!(storage.shared_slice(), &[1, 2, 3, 7, 7]);
+        assert_eq!(
+            storage.try_extend_from_slice(&[8]),
+            Err(Error::CapacityExceeded { capacity: 5 })
+        );
+        assert_eq!(
+            storage.try_resize(6, 0),
+            Err(Error::CapacityExceeded { capacity: 5 })
+        );
+        storage.truncate(2);
+        assert_eq!(storage.len(), 2);
+        // Unused slots don't matter for comparison.
+        assert_eq!(storage, ArrayVecStorage::<u16, 2>::from_slice(&[1, 2]));
+        assert!(ArrayVecStorage::<u16, 1>::try_from_iter(0..2).is_err());
+    }
+
+    pub fn read_write_and_iterate() {
+        let mut storage = ArrayVecStorage::<i32, 3>::try_from_iter([3, 4]).unwrap();
+        assert_eq!(storage.get(1), 4);
+        assert!(storage.check_and_set(0, &5));
+        assert_eq!(
+            storage.try_set(2, &0),
+            Err(Error::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        for item in storage.iter_mut() {
+            *item *= 10;
+        }
+        assert_eq!(&storage[..], &[50, 40]);
+        assert!(storage.iter().eq([50, 40].iter()));
+        assert_eq!(storage.first(), Some(&50));
+    }
+
+    /// Owned iteration covers used items only.
+    pub fn owned_iteration() {
+        let mut storage = ArrayVecStorage::<u8, 4>::from_slice(&[1, 2, 3, 4]);
+        storage.truncate(3);
+        assert_eq!(storage.clone().into_iter().len(), 3);
+        assert!(storage.into_iter().rev().eq([3, 2, 1]));
+    }
+
+    /// Counts its drops. It has no `Default`.
+    #[derive(Clone, PartialEq)]
+    struct Counted<'c>(&'c Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    /// Only used slots get dropped, and each of them once.
+    pub fn items_without_default() {
+        let drops = Cell::new(0);
+        let mut storage = ArrayVecStorage::<Counted, 4>::new();
+        storage.push(Counted(&drops));
+        storage.resize(3, Counted(&drops));
+        assert_eq!(drops.get(), 1);
+        let clone = storage.clone();
+        drop(storage.remove(0));
+        assert_eq!(drops.get(), 2);
+        storage.truncate(1);
+        assert_eq!(drops.get(), 3);
+        drop(storage);
+        assert_eq!(drops.get(), 4);
+        assert_eq!(clone.len(), 3);
+        drop(clone);
+        assert_eq!(drops.get(), 7);
+
+        // Owned iteration drops the items that it didn't return.
+        let mut iter =
+            ArrayVecStorage::<Counted, 4>::try_from_iter([Counted(&drops), Counted(&drops)])
+                .unwrap()
+                .into_iter();
+        drop(iter.next());
+        assert_eq!(drops.get(), 8);
+        drop(iter);
+        assert_eq!(drops.get(), 9);
+    }
+}
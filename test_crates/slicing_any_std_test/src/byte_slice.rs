@@ -0,0 +1,230 @@
+/// Helpers for packed bool storage. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod bool_storage {
+    use slicing::byte_slice::ByteSliceBoolStorage;
+    use slicing::Error;
+    use slicing::slices::{
+        BoolSlice, SliceBacked, SliceBackedChoice, SliceDefault, SliceRead, SliceWrite,
+    };
+
+    /// Assert that `packed` has (at least) the same leading items as `slice`.
+    fn assert_equal_items<'a, S: SliceDefault<'a, bool, N>, const N: usize>(
+        packed: &S,
+        slice: &[bool],
+    ) {
+        for i in 0..slice.len() {
+            assert_eq!(packed.get(i), slice[i]);
+        }
+    }
+
+    pub fn from_value_fn_iter_to_array() {
+        assert_equal_items(
+            &ByteSliceBoolStorage::<10>::from_value_to_array(&true),
+            &[true; 10],
+        );
+        assert_equal_items(
+            &ByteSliceBoolStorage::<3>::from_value_to_array(&false),
+            &[false; 3],
+        );
+
+        let items = [true, false, false, true, true, false, true, false, true];
+        assert_equal_items(
+            &ByteSliceBoolStorage::<9>::from_iter_to_array(items.iter().cloned()),
+            &items,
+        );
+
+        let mut i = 0;
+        let storage = ByteSliceBoolStorage::<9>::from_fn_to_array(|| {
+            i += 1;
+            items[i - 1]
+        });
+        assert_equal_items(&storage, &items);
+    }
+
+    pub fn len_and_iter_stop_at_n() {
+        let items = [
+            true, false, true, true, false, false, false, true, false, true,
+        ];
+        let storage = ByteSliceBoolStorage::<10>::from_iter_to_array(items.iter().cloned());
+        assert_eq!(storage.len(), 10);
+        assert!(!storage.is_empty());
+
+        let iter = storage.iter();
+        assert_eq!(iter.len(), 10);
+        for (bit, item) in iter.zip(items.iter()) {
+            assert_eq!(bit, item);
+        }
+        assert_eq!(storage.iter().count(), 10);
+        for (bit, item) in storage.iter().rev().zip(items.iter().rev()) {
+            assert_eq!(bit, item);
+        }
+
+        let mut iter = storage.iter();
+        assert_eq!(iter.next(), Some(&true));
+        assert_eq!(iter.next_back(), Some(&true));
+        assert_eq!(iter.next_back(), Some(&false));
+        assert_eq!(iter.len(), 7);
+    }
+
+    pub fn pack_unpack_array() {
+        let items = [true, false, false, true, true, false, true, false, true];
+        let packed = ByteSliceBoolStorage::<9>::pack(&items, SliceBackedChoice::Array);
+        assert_equal_items(&packed, &items);
+
+        let bool_slice = BoolSlice::<9>::from_shared(&items);
+        let packed =
+            ByteSliceBoolStorage::<9>::pack_bool_slice(&bool_slice, SliceBackedChoice::Array);
+        let unpacked = packed.unpack(SliceBackedChoice::Array);
+        assert_eq!(unpacked.shared_slice(), &items);
+
+        // Edit a mask unpacked, then store it packed again - with no allocation.
+        let mut mask = [false; 9];
+        packed.unpack_into(&mut mask);
+        mask[1] = true;
+        let mut packed = packed;
+        packed.pack_from(&mask);
+        assert!(packed.get(1));
+        assert_equal_items(&packed, &mask);
+
+        let packed = ByteSliceBoolStorage::<9>::from_array(items);
+        assert_equal_items(&packed, &items);
+        let packed = ByteSliceBoolStorage::<9>::from_shared(&items);
+        assert_equal_items(&packed, &items);
+    }
+
+    /// Packing copies, so constructors that promise write-through fail.
+    pub fn pack_copy_of_fewer_items() {
+        let items = [true, false, true];
+        // Vec-based with heap, array-based (with unused bits) without.
+        let packed = ByteSliceBoolStorage::<16>::from_shared(&items);
+        assert_eq!(packed.len(), 3);
+        assert_equal_items(&packed, &items);
+        assert_eq!(
+            ByteSliceBoolStorage::<2>::try_from_shared(&items).map(|packed| packed.len()),
+            if cfg!(all(feature = "no_std", not(feature = "no_std_heap"))) {
+                Err(Error::LengthMismatch {
+                    expected: 2,
+                    actual: 3,
+                })
+            } else {
+                Ok(3)
+            }
+        );
+
+        let mut items = items;
+        assert_eq!(
+            ByteSliceBoolStorage::<3>::try_from_mutable(&mut items).err(),
+            Some(Error::NotSliceBacked)
+        );
+    }
+
+    pub fn shared_and_mutable_bytes() {
+        // 12 bits, followed by 4 (set) padding bits and by an extra byte.
+        let bytes = [0b1000_0001u8, 0b1111_0010, 0xFF];
+        let view = ByteSliceBoolStorage::<0>::from_shared_bytes(&bytes, 12);
+        assert_eq!(view.len(), 12);
+        assert!(view.get(0) && view.get(7) && view.get(9));
+        assert!(!view.get(8));
+        assert_eq!(view.iter().filter(|bit| **bit).count(), 3);
+        assert_eq!(view.count_ones(), 3);
+        assert_eq!(view.last_one(), Some(9));
+
+        let mut bytes = bytes;
+        let mut view = ByteSliceBoolStorage::<0>::from_mutable_bytes(&mut bytes, 12);
+        view.set(8, &true);
+        view.not_assign();
+        assert_eq!(view.count_ones(), 8);
+        // Modified in place, with padding bits and the extra byte untouched.
+        assert_eq!(bytes, [0b0111_1110, 0b1111_1100, 0xFF]);
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn from_value_fn_iter_to_vec() {
+        assert_equal_items(
+            &ByteSliceBoolStorage::<0>::from_value_to_vec(&true, 11),
+            &[true; 11],
+        );
+
+        let items = [
+            false, true, true, false, false, true, false, true, true, true,
+        ];
+        assert_equal_items(
+            &ByteSliceBoolStorage::<0>::from_iter_to_vec(items.iter().cloned()),
+            &items,
+        );
+
+        let mut i = 0;
+        let storage = ByteSliceBoolStorage::<0>::from_fn_to_vec(
+            || {
+                i += 1;
+                items[i - 1]
+            },
+            items.len(),
+        );
+        assert_equal_items(&storage, &items);
+        assert_eq!(storage.len(), items.len());
+
+        let storage = ByteSliceBoolStorage::<0>::from_default_to_vec(10);
+        assert_eq!(storage.len(), 10);
+        assert_eq!(storage.iter().len(), 10);
+        assert!(ByteSliceBoolStorage::<0>::from_iter_to_vec(core::iter::empty()).is_empty());
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn pack_unpack_vec() {
+        let items = [
+            false, true, true, false, false, true, false, true, true, true, false,
+        ];
+        let packed = ByteSliceBoolStorage::<0>::pack(&items, SliceBackedChoice::Vec);
+        assert_eq!(packed.len(), items.len());
+        assert_equal_items(&packed, &items);
+        let unpacked = packed.unpack(SliceBackedChoice::Vec);
+        assert_eq!(unpacked.shared_slice(), &items);
+
+        let packed = ByteSliceBoolStorage::<0>::from_vec(items.to_vec());
+        assert_equal_items(&packed, &items);
+        // Not N items: packed into a Vec.
+        let packed = ByteSliceBoolStorage::<4>::from_shared(&items);
+        assert_eq!(packed.len(), items.len());
+        assert_equal_items(&packed, &items);
+    }
+}
+
+#[cfg(test)]
+mod bool_storage_tests {
+    #[test]
+    fn from_value_fn_iter_to_array() {
+        super::bool_storage::from_value_fn_iter_to_array();
+    }
+
+    #[test]
+    fn len_and_iter_stop_at_n() {
+        super::bool_storage::len_and_iter_stop_at_n();
+    }
+
+    #[test]
+    fn pack_unpack_array() {
+        super::bool_storage::pack_unpack_array();
+    }
+
+    #[test]
+    fn pack_copy_of_fewer_items() {
+        super::bool_storage::pack_copy_of_fewer_items();
+    }
+
+    #[test]
+    fn shared_and_mutable_bytes() {
+        super::bool_storage::shared_and_mutable_bytes();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn from_value_fn_iter_to_vec() {
+        super::bool_storage::from_value_fn_iter_to_vec();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn pack_unpack_vec() {
+        super::bool_storage::pack_unpack_vec();
+    }
+}
@@ -0,0 +1,101 @@
+/// Helpers for `CowStorage` and `SliceMakeMut`. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod cow {
+    use slicing::cow::CowStorage;
+    use slicing::slices::{ByteSlice, SliceBacked, SliceDefault, SliceMakeMut, SliceWrite};
+
+    /// Writes only sometimes, so the caller can't tell up front whether it
+    /// needs an owned storage.
+    fn clear_odd<S: SliceWrite<u8>>(slice: &mut S) {
+        for i in 0..slice.len() {
+            if slice.get(i) % 2 == 1 {
+                slice.set(i, &0);
+            }
+        }
+    }
+
+    pub fn borrows_until_modified() {
+        let items = [2u8, 4, 6];
+        let mut cow = CowStorage::new(ByteSlice::<3>::from_shared(&items));
+        clear_odd(&mut cow);
+        assert!(matches!(*cow, ByteSlice::Shared(_)));
+        assert_eq!(cow.try_check_and_set(0, &2), Ok(false));
+        assert!(matches!(*cow, ByteSlice::Shared(_)));
+
+        // It has `N` items, so it becomes Array-based.
+        assert_eq!(cow.try_check_and_set(0, &1), Ok(true));
+        assert!(matches!(*cow, ByteSlice::Array(_)));
+        clear_odd(&mut cow);
+        assert_eq!(cow.shared_slice(), &[0, 4, 6]);
+        assert_eq!(items, [2, 4, 6]);
+        assert_eq!(
+            cow.try_set(3, &0),
+            Err(slicing::Error::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert!(matches!(cow.into_inner(), ByteSlice::Array(_)));
+    }
+
+    pub fn clone_shared() {
+        let items = [1u8, 2];
+        let mut cow = CowStorage::new(ByteSlice::<2>::from_shared(&items));
+        let copy = cow.clone();
+        assert!(matches!(*copy, ByteSlice::Shared(shared) if shared.as_ptr() == items.as_ptr()));
+        cow.set(0, &0);
+        assert_eq!(copy.shared_slice(), &[1, 2]);
+        assert_eq!(cow.clone(), cow);
+    }
+
+    pub fn make_mut() {
+        let items = [1u8, 2];
+        let mut array_sized = ByteSlice::<2>::from_shared(&items);
+        array_sized.make_mut()[0] = 3;
+        assert!(matches!(array_sized, ByteSlice::Array([3, 2])));
+
+        let mut other_size = ByteSlice::<3>::from_shared(&items);
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        {
+            other_size.make_mut()[1] = 4;
+            assert!(matches!(other_size, ByteSlice::Vec(_)));
+            assert_eq!(other_size.shared_slice(), &[1, 4]);
+        }
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        assert_eq!(
+            other_size.try_make_mut().err(),
+            Some(slicing::Error::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        // Already modifiable instances stay as they are.
+        let mut array = [5u8];
+        let mut mutable = ByteSlice::<0>::from_mutable(&mut array);
+        mutable.make_mut()[0] = 6;
+        assert!(matches!(mutable, ByteSlice::Mutable(_)));
+        assert_eq!(array, [6]);
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn shared_rc() {
+        #[cfg(feature = "no_std_heap")]
+        extern crate alloc;
+        #[cfg(feature = "no_std_heap")]
+        use alloc::rc::Rc;
+        #[cfg(not(feature = "no_std"))]
+        use std::rc::Rc;
+
+        let rc: Rc<[u8]> = Rc::from([1, 3, 5, 7]);
+        let mut cow = CowStorage::new(ByteSlice::<0>::Rc(Rc::clone(&rc)));
+        clear_odd(&mut cow);
+        // It copied the items into a new, unique `Rc`.
+        assert!(matches!(&*cow, ByteSlice::Rc(copy) if !Rc::ptr_eq(copy, &rc)));
+        assert_eq!(cow.shared_slice(), &[0, 0, 0, 0]);
+        assert_eq!(&*rc, &[1, 3, 5, 7]);
+
+        // A unique `Rc` is modified in place.
+        let mut unique = ByteSlice::<0>::Rc(rc);
+        let before = unique.shared_slice().as_ptr();
+        unique.make_mut()[0] = 9;
+        assert_eq!(unique.shared_slice().as_ptr(), before);
+        assert_eq!(unique.shared_slice(), &[9, 3, 5, 7]);
+    }
+}
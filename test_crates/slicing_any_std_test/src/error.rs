@@ -0,0 +1,188 @@
+/// Helpers for the fallible (`try_*`) API. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod fallible {
+    use slicing::packed_bool::PackedBoolStorage;
+    use slicing::packed_uint::PackedUintStorage;
+    use slicing::slices::{
+        ByteSlice, CollectTo, SliceBacked, SliceBackedChoice, SliceDefault, SliceRead,
+        SliceStorage, SliceWrite,
+    };
+    use slicing::Error;
+
+    pub fn get_and_set() {
+        let mut bytes = ByteSlice::<4>::from_array([1, 2, 3, 4]);
+        assert_eq!(bytes.len(), 4);
+        assert_eq!(bytes.try_get(3), Ok(4));
+        assert_eq!(
+            bytes.try_get(4),
+            Err(Error::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert_eq!(bytes.try_check_and_set(0, &9), Ok(true));
+        assert_eq!(bytes.try_check_and_set(0, &9), Ok(false));
+        assert_eq!(
+            bytes.try_set(4, &0),
+            Err(Error::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert_eq!(bytes.try_mutable_slice().map(|slice| slice.len()), Ok(4));
+
+        let items = [1u8, 2];
+        let mut shared = ByteSlice::<0>::from_shared(&items);
+        assert_eq!(shared.try_get(1), Ok(2));
+        assert_eq!(shared.try_set(0, &5), Err(Error::SharedNotMutable));
+        assert_eq!(
+            shared.try_mutable_slice().err(),
+            Some(Error::SharedNotMutable)
+        );
+        assert_eq!(shared.try_shared_slice(), Ok(&items[..]));
+    }
+
+    pub fn constructors() {
+        let bytes = ByteSlice::<3>::try_from_iter_to_array(1..4).unwrap();
+        assert_eq!(bytes.shared_slice(), &[1, 2, 3]);
+        assert_eq!(
+            ByteSlice::<3>::try_from_iter_to_array(1..3).err(),
+            Some(Error::NotEnoughItems {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            ByteSlice::<3>::try_from_default(3, SliceBackedChoice::Shared).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Shared))
+        );
+        assert_eq!(
+            ByteSlice::<3>::try_from_value(&7, 3, SliceBackedChoice::Array)
+                .unwrap()
+                .shared_slice(),
+            &[7, 7, 7]
+        );
+
+        assert_eq!(
+            (1u8..3)
+                .try_collect_to::<SliceStorage<u8, 3>, 3>(SliceBackedChoice::Array)
+                .err(),
+            Some(Error::NotEnoughItems {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            (1u8..4)
+                .try_collect_to::<SliceStorage<u8, 3>, 3>(SliceBackedChoice::Mutable)
+                .err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Mutable))
+        );
+
+        // Without heap, Inline-based ones can't grow beyond `N`.
+        let inline = ByteSlice::<2>::try_from_default(3, SliceBackedChoice::Inline);
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        assert_eq!(inline.unwrap().shared_slice(), &[0, 0, 0]);
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        assert_eq!(inline.err(), Some(Error::CapacityExceeded { capacity: 2 }));
+        assert_eq!(
+            ByteSlice::<2>::try_from_value(&5, 2, SliceBackedChoice::Inline)
+                .unwrap()
+                .shared_slice(),
+            &[5, 5]
+        );
+
+        let items = [1u8, 2];
+        assert_eq!(
+            ByteSlice::<3>::from_shared(&items)
+                .try_to_array_based()
+                .err(),
+            Some(Error::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    pub fn packed() {
+        let mut bools =
+            PackedBoolStorage::<u8, 10>::try_from_iter_to_array(core::iter::repeat(true).take(10))
+                .unwrap();
+        assert_eq!(bools.try_check_and_set(9, &false), Ok(true));
+        assert_eq!(
+            bools.try_get(10),
+            Err(Error::IndexOutOfBounds { index: 10, len: 10 })
+        );
+        assert_eq!(bools.try_shared_slice().err(), Some(Error::NotSliceBacked));
+        assert!(bools.try_to_array_based().is_ok());
+
+        let blocks = [0xFFu8, 0x03];
+        let mut shared = PackedBoolStorage::<u8, 0>::from_shared_blocks(&blocks, 10);
+        assert_eq!(shared.try_get(9), Ok(true));
+        assert_eq!(shared.try_set(9, &false), Err(Error::SharedNotMutable));
+
+        let mut uints = PackedUintStorage::<u8, 3, 4>::from_array([1, 2, 3, 4]);
+        assert_eq!(uints.try_set_value(0, 7), Ok(true));
+        assert_eq!(
+            uints.try_set(0, &8),
+            Err(Error::ValueDoesNotFit { value: 8, bits: 3 })
+        );
+        assert_eq!(uints.try_get(0), Ok(7));
+        assert_eq!(
+            PackedUintStorage::<u8, 3, 2>::try_from_shared(&[7, 8]).err(),
+            Some(Error::ValueDoesNotFit { value: 8, bits: 3 })
+        );
+
+        let bools = [true, false];
+        assert_eq!(
+            PackedBoolStorage::<u8, 3>::try_pack(&bools, SliceBackedChoice::Array).err(),
+            Some(Error::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            PackedBoolStorage::<u8, 2>::try_pack(&bools, SliceBackedChoice::Shared).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Shared))
+        );
+        let packed = PackedBoolStorage::<u8, 2>::from_array(bools);
+        assert_eq!(
+            packed
+                .try_unpack(SliceBackedChoice::Array)
+                .unwrap()
+                .shared_slice(),
+            &bools
+        );
+        assert_eq!(
+            packed.try_unpack(SliceBackedChoice::Mutable).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Mutable))
+        );
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn vec_based() {
+        use slicing::slices::SliceVec;
+
+        let mut array = ByteSlice::<2>::from_array([1, 2]);
+        assert_eq!(array.try_mutable_vec().err(), Some(Error::NotVecBased));
+
+        assert_eq!(
+            ByteSlice::<0>::from_value(&5, 3, SliceBackedChoice::Vec).shared_slice(),
+            &[5, 5, 5]
+        );
+
+        let mut vec = array.to_vec_based();
+        vec.try_mutable_vec().unwrap().push(3);
+        let narr = vec.to_non_array_vec_based();
+        assert_eq!(
+            ByteSlice::<2>::try_from_non_array_vec_based(&narr, &SliceBackedChoice::Array).err(),
+            Some(Error::LengthMismatch {
+                expected: 2,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            ByteSlice::<3>::try_from_non_array_vec_based(&narr, &SliceBackedChoice::Array)
+                .unwrap()
+                .shared_slice(),
+            &[1, 2, 3]
+        );
+        assert_eq!(
+            ByteSlice::<2>::try_from_non_array_vec_based(&narr, &SliceBackedChoice::VecRef).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::VecRef))
+        );
+    }
+}
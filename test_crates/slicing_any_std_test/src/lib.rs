@@ -1,8 +1,24 @@
 #![cfg_attr(feature = "no_std", no_std)]
+// Required by (client code of) `slicing::byte_slice::ByteSliceBoolStorage`.
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
 
 // Do NOT use #[cfg(test)] under `any_std/src`, so that we can import & reuse it from `../ok_std/` and `../no_std_*/`.
+pub mod array_vec;
 pub mod bool_slice;
+pub mod byte_slice;
+pub mod cow;
+pub mod error;
+pub mod packed_bool;
+pub mod packed_uint;
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+pub mod roaring;
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+pub mod run_length;
+pub mod simd;
 pub mod slices;
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+pub mod small_vec;
 
 #[cfg(all(feature = "no_std", feature = "std"))]
 compile_error!("std and no_std are mutually exclusive! Use maximum one of those two.");
@@ -0,0 +1,426 @@
+/// Helpers for packed bool storage with non-byte blocks. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod bool_storage {
+    use core::sync::atomic::AtomicU8;
+    use slicing::byte_slice::AtomicByteSliceBoolStorage;
+    use slicing::packed_bool::atomic::AtomicPackedBoolStorage;
+    use slicing::packed_bool::{Lsb0, Msb0, PackedBoolStorage};
+    use slicing::slices::{SliceBackedChoice, SliceDefault, SliceRead, SliceWrite};
+    #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+    use slicing::Error;
+
+    /// Bit at `index` of a (pseudo-random) pattern.
+    fn pattern(index: usize) -> bool {
+        index % 3 == 0 || index % 7 == 1
+    }
+
+    pub fn wide_blocks_get_set() {
+        let mut storage = PackedBoolStorage::<u64, 100>::from_iter_to_array((0..100).map(pattern));
+        assert_eq!(storage.len(), 100);
+        for i in 0..100 {
+            assert_eq!(storage.get(i), pattern(i));
+        }
+        // Across the block boundary.
+        assert!(storage.check_and_set(64, &false));
+        assert!(!storage.check_and_set(64, &false));
+        storage.set(63, &true);
+        assert!(storage.get(63));
+        assert!(!storage.get(64));
+
+        let storage = PackedBoolStorage::<usize, 70>::from_value_to_array(&true);
+        assert_eq!(storage.iter().filter(|bit| **bit).count(), 70);
+
+        let storage = PackedBoolStorage::<u16, 17>::from_iter_to_array((0..17).map(pattern));
+        for (i, bit) in storage.iter().enumerate().rev() {
+            assert_eq!(*bit, pattern(i));
+        }
+    }
+
+    fn other_pattern(index: usize) -> bool {
+        index % 2 == 0
+    }
+
+    pub fn bitwise_ops() {
+        let left = PackedBoolStorage::<u8, 21>::from_iter_to_array((0..21).map(pattern));
+        let right = PackedBoolStorage::<u8, 21>::from_iter_to_array((0..21).map(other_pattern));
+
+        let and = &left & &right;
+        let or = &left | &right;
+        let xor = &left ^ &right;
+        let and_not = left.and_not(&right);
+        let not = !&left;
+        for i in 0..21 {
+            assert_eq!(and.get(i), pattern(i) && other_pattern(i));
+            assert_eq!(or.get(i), pattern(i) || other_pattern(i));
+            assert_eq!(xor.get(i), pattern(i) != other_pattern(i));
+            assert_eq!(and_not.get(i), pattern(i) && !other_pattern(i));
+            assert_eq!(not.get(i), !pattern(i));
+        }
+        // Complement doesn't set padding bits: double complement is identity,
+        // and so is xor with an all-false storage.
+        let mut twice = !not;
+        twice ^= &PackedBoolStorage::<u8, 21>::from_value_to_array(&false);
+        for i in 0..21 {
+            assert_eq!(twice.get(i), pattern(i));
+        }
+
+        let mut wide = PackedBoolStorage::<u64, 130>::from_iter_to_array((0..130).map(pattern));
+        wide &= &PackedBoolStorage::<u64, 130>::from_iter_to_array((0..130).map(other_pattern));
+        wide.not_assign();
+        for i in 0..130 {
+            assert_eq!(wide.get(i), !(pattern(i) && other_pattern(i)));
+        }
+    }
+
+    /// Naive rank: number of set bits before `index`.
+    fn naive_rank(index: usize) -> usize {
+        (0..index).filter(|&i| pattern(i)).count()
+    }
+
+    pub fn count_rank_select() {
+        let storage = PackedBoolStorage::<u64, 1000>::from_iter_to_array((0..1000).map(pattern));
+        let ones = naive_rank(1000);
+        assert_eq!(storage.count_ones(), ones);
+        assert_eq!(storage.count_zeros(), 1000 - ones);
+
+        let mut k = 0;
+        for i in 0..=1000 {
+            assert_eq!(storage.rank(i), naive_rank(i));
+            if i < 1000 && pattern(i) {
+                assert_eq!(storage.select(k), Some(i));
+                k += 1;
+            }
+        }
+        assert_eq!(storage.select(ones), None);
+
+        // Padding bits are not counted.
+        let storage = PackedBoolStorage::<u8, 11>::from_value_to_array(&true);
+        assert_eq!(storage.count_ones(), 11);
+        assert_eq!(storage.rank(11), 11);
+        assert_eq!(storage.select(10), Some(10));
+        assert_eq!(storage.select(11), None);
+    }
+
+    pub fn iter_ones_zeros_and_search() {
+        let storage = PackedBoolStorage::<u16, 300>::from_iter_to_array((0..300).map(pattern));
+        assert!(storage.iter_ones().eq((0..300).filter(|&i| pattern(i))));
+        assert!(storage.iter_zeros().eq((0..300).filter(|&i| !pattern(i))));
+
+        for from in 0..=300 {
+            assert_eq!(storage.next_one(from), (from..300).find(|&i| pattern(i)));
+            assert_eq!(storage.next_zero(from), (from..300).find(|&i| !pattern(i)));
+            assert_eq!(
+                storage.prev_one(from),
+                (0..=from.min(299)).rev().find(|&i| pattern(i))
+            );
+        }
+        assert_eq!(storage.first_one(), Some(0));
+        assert_eq!(storage.first_zero(), Some(2));
+        assert_eq!(storage.last_one(), (0..300).rev().find(|&i| pattern(i)));
+
+        // Sparse: a single bit far in, and padding is never reported.
+        let mut sparse = PackedBoolStorage::<u64, 1000>::from_value_to_array(&false);
+        assert_eq!(sparse.iter_ones().next(), None);
+        assert_eq!(sparse.first_one(), None);
+        assert_eq!(sparse.last_zero(), Some(999));
+        assert_eq!(sparse.iter_zeros().count(), 1000);
+        sparse.set(777, &true);
+        assert!(sparse.iter_ones().eq([777].into_iter()));
+        assert_eq!(sparse.prev_one(999), Some(777));
+        assert_eq!(sparse.prev_one(776), None);
+
+        let full = PackedBoolStorage::<u8, 13>::from_value_to_array(&true);
+        assert_eq!(full.first_zero(), None);
+        assert_eq!(full.iter_zeros().next(), None);
+        assert_eq!(full.last_one(), Some(12));
+    }
+
+    pub fn range_ops() {
+        let mut storage = PackedBoolStorage::<u8, 50>::from_value_to_array(&false);
+        storage.set_range(3..29);
+        for i in 0..50 {
+            assert_eq!(storage.get(i), (3..29).contains(&i));
+        }
+        storage.toggle_range(0..8);
+        storage.clear_range(20..21);
+        storage.clear_range(5..5);
+        for i in 0..50 {
+            let expected = if i < 8 {
+                i < 3
+            } else {
+                (8..29).contains(&i) && i != 20
+            };
+            assert_eq!(storage.get(i), expected);
+        }
+        assert_eq!(storage.count_ones_in(0..50), storage.count_ones());
+        assert_eq!(storage.count_ones_in(2..22), 1 + 13);
+        assert!(storage.any_in(19..21));
+        assert!(!storage.any_in(3..8));
+        assert!(!storage.any_in(10..10));
+        assert!(storage.all_in(8..20));
+        assert!(!storage.all_in(8..21));
+        assert!(storage.all_in(30..30));
+
+        let mut wide = PackedBoolStorage::<u64, 300>::from_iter_to_array((0..300).map(pattern));
+        wide.fill_range(10..290, true);
+        assert!(wide.all_in(10..290));
+        assert_eq!(
+            wide.count_ones_in(0..300),
+            280 + naive_rank(10) + (290..300).filter(|&i| pattern(i)).count()
+        );
+        wide.toggle_range(0..300);
+        assert!(!wide.any_in(10..290));
+    }
+
+    pub fn msb0_order() {
+        let mut msb0 = PackedBoolStorage::<u8, 12, Msb0>::from_value_to_array(&false);
+        msb0.set(0, &true);
+        msb0.set(9, &true);
+        msb0.set(11, &true);
+        assert_eq!(msb0.as_blocks(), &[0b1000_0000, 0b0101_0000]);
+
+        let mut lsb0 = PackedBoolStorage::<u8, 12>::from_value_to_array(&false);
+        lsb0.set(0, &true);
+        lsb0.set(9, &true);
+        lsb0.set(11, &true);
+        assert_eq!(lsb0.as_blocks(), &[0b0000_0001, 0b0000_1010]);
+
+        // Same logical behaviour for both orders.
+        let msb0 = PackedBoolStorage::<u16, 200, Msb0>::from_iter_to_array((0..200).map(pattern));
+        let lsb0 = PackedBoolStorage::<u16, 200, Lsb0>::from_iter_to_array((0..200).map(pattern));
+        assert!(msb0.iter().eq(lsb0.iter()));
+        assert!(msb0.iter_ones().eq(lsb0.iter_ones()));
+        assert!(msb0.iter_zeros().eq(lsb0.iter_zeros()));
+        for i in 0..=200 {
+            assert_eq!(msb0.rank(i), lsb0.rank(i));
+            assert_eq!(msb0.select(i), lsb0.select(i));
+            assert_eq!(msb0.next_one(i), lsb0.next_one(i));
+            assert_eq!(msb0.prev_zero(i), lsb0.prev_zero(i));
+            assert_eq!(msb0.count_ones_in(i / 2..i), lsb0.count_ones_in(i / 2..i));
+        }
+        let mut complement = !&msb0;
+        complement.set_range(5..40);
+        assert_eq!(
+            complement.count_ones(),
+            (0..200)
+                .filter(|&i| (5..40).contains(&i) || !pattern(i))
+                .count()
+        );
+        assert_eq!(complement.last_one(), lsb0.last_zero());
+    }
+
+    pub fn atomic_set_clear_toggle() {
+        let storage = AtomicByteSliceBoolStorage::<20>::new_array();
+        assert_eq!(storage.len(), 20);
+        assert!(storage.test_and_set(9));
+        assert!(!storage.test_and_set(9));
+        storage.set(19);
+        assert!(storage.get(9) && storage.get(19));
+        assert!(!storage.fetch_toggle(0));
+        assert!(storage.fetch_toggle(0));
+        assert!(!storage.get(0));
+        storage.clear(9);
+        assert!(!storage.test_and_clear(9));
+        assert_eq!(storage.count_ones(), 1);
+
+        let packed = PackedBoolStorage::<usize, 70, Msb0>::from_iter_to_array((0..70).map(pattern));
+        let storage = AtomicPackedBoolStorage::<usize, 70, Msb0>::from_packed(&packed);
+        assert_eq!(storage.count_ones(), packed.count_ones());
+        assert!(storage.test_and_clear(69));
+        let snapshot = storage.to_packed();
+        for i in 0..70 {
+            assert_eq!(snapshot.get(i), pattern(i) && i != 69);
+        }
+
+        // Other than `N` bits need heap.
+        let atomics = [AtomicU8::new(0b1), AtomicU8::new(0b1_0000)];
+        let storage = AtomicByteSliceBoolStorage::<8>::from_shared_atomics(&atomics, 13);
+        let packed = storage.try_to_packed();
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        assert_eq!(packed.unwrap().as_blocks(), &[0b1, 0b1_0000]);
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        assert_eq!(
+            packed.err(),
+            Some(Error::LengthMismatch {
+                expected: 8,
+                actual: 13
+            })
+        );
+        let packed = PackedBoolStorage::<u8, 4>::from_iter_to_array([true; 4].into_iter());
+        let storage = AtomicByteSliceBoolStorage::<8>::try_from_packed(&packed);
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        assert_eq!(storage.unwrap().count_ones(), 4);
+        #[cfg(all(feature = "no_std", not(feature = "no_std_heap")))]
+        assert_eq!(
+            storage.err(),
+            Some(Error::LengthMismatch {
+                expected: 8,
+                actual: 4
+            })
+        );
+    }
+
+    /// Concurrent threads mark bits, with no lock.
+    #[cfg(not(feature = "no_std"))]
+    pub fn atomic_threads() {
+        let storage = AtomicPackedBoolStorage::<u64, 1000>::new_array();
+        let newly_set = std::sync::atomic::AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for thread_index in 0..4 {
+                let (storage, newly_set) = (&storage, &newly_set);
+                scope.spawn(move || {
+                    // Threads overlap: each bit is marked by 2 threads.
+                    for i in (thread_index % 2..1000).step_by(2) {
+                        if storage.test_and_set(i) {
+                            newly_set.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+        assert_eq!(newly_set.into_inner(), 1000);
+        assert_eq!(storage.count_ones(), 1000);
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn rank_select_index() {
+        use slicing::packed_bool::rank_select::RankSelectIndex;
+
+        let storage = PackedBoolStorage::<u8, 3000>::from_iter_to_array((0..3000).map(pattern));
+        let index = RankSelectIndex::new(&storage);
+        assert_eq!(index.count_ones(), storage.count_ones());
+        for i in 0..=3000 {
+            assert_eq!(index.rank(i), storage.rank(i));
+        }
+        for k in 0..=index.count_ones() {
+            assert_eq!(index.select(k), storage.select(k));
+        }
+
+        let storage = PackedBoolStorage::<u64, 0>::from_iter_to_vec((0..1500).map(|i| i > 1100));
+        let index = RankSelectIndex::new(&storage);
+        assert_eq!(index.count_ones(), 399);
+        assert_eq!(index.select(0), Some(1101));
+        assert_eq!(index.rank(1500), 399);
+        assert_eq!(index.rank(1024), 0);
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn bitwise_ops_vec_based() {
+        let array_based = PackedBoolStorage::<u32, 45>::from_iter_to_array((0..45).map(pattern));
+        let vec_based = PackedBoolStorage::<u32, 0>::from_iter_to_vec((0..45).map(other_pattern));
+
+        let union = &vec_based | &array_based;
+        assert_eq!(union.len(), 45);
+        let mut intersection = array_based.and(&vec_based);
+        for i in 0..45 {
+            assert_eq!(union.get(i), pattern(i) || other_pattern(i));
+            assert_eq!(intersection.get(i), pattern(i) && other_pattern(i));
+        }
+        intersection |= &union;
+        for i in 0..45 {
+            assert_eq!(intersection.get(i), union.get(i));
+        }
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn atomic_vec_based() {
+        let storage = AtomicByteSliceBoolStorage::<0>::new_vec(13);
+        assert_eq!(storage.len(), 13);
+        storage.set(12);
+        let packed = storage.to_packed();
+        assert_eq!(packed.len(), 13);
+        assert_eq!(packed.as_blocks(), &[0, 0b1_0000]);
+
+        // Not N bits: copied to a Vec.
+        let storage = AtomicByteSliceBoolStorage::<4>::from_packed(&packed);
+        assert_eq!(storage.len(), 13);
+        assert!(storage.get(12));
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn wide_blocks_vec_based() {
+        let storage = PackedBoolStorage::<u32, 0>::from_iter_to_vec((0..75).map(pattern));
+        assert_eq!(storage.len(), 75);
+        for i in 0..75 {
+            assert_eq!(storage.get(i), pattern(i));
+        }
+
+        let array_based = PackedBoolStorage::<u32, 40>::from_iter_to_array((0..40).map(pattern));
+        let vec_based = array_based.to_non_array_vec_based();
+        assert_eq!(vec_based.len(), 40);
+        let array_based = PackedBoolStorage::<u32, 40>::from_non_array_vec_based(
+            &vec_based,
+            &SliceBackedChoice::Array,
+        );
+        for i in 0..40 {
+            assert_eq!(array_based.get(i), pattern(i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod bool_storage_tests {
+    #[test]
+    fn wide_blocks_get_set() {
+        super::bool_storage::wide_blocks_get_set();
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        super::bool_storage::bitwise_ops();
+    }
+
+    #[test]
+    fn count_rank_select() {
+        super::bool_storage::count_rank_select();
+    }
+
+    #[test]
+    fn iter_ones_zeros_and_search() {
+        super::bool_storage::iter_ones_zeros_and_search();
+    }
+
+    #[test]
+    fn range_ops() {
+        super::bool_storage::range_ops();
+    }
+
+    #[test]
+    fn msb0_order() {
+        super::bool_storage::msb0_order();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn rank_select_index() {
+        super::bool_storage::rank_select_index();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn bitwise_ops_vec_based() {
+        super::bool_storage::bitwise_ops_vec_based();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn wide_blocks_vec_based() {
+        super::bool_storage::wide_blocks_vec_based();
+    }
+
+    #[test]
+    fn atomic_set_clear_toggle() {
+        super::bool_storage::atomic_set_clear_toggle();
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn atomic_threads() {
+        super::bool_storage::atomic_threads();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn atomic_vec_based() {
+        super::bool_storage::atomic_vec_based();
+    }
+}
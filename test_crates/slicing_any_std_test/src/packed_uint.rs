@@ -0,0 +1,131 @@
+/// Helpers for packed fixed-width unsigned integer storage. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod uint_storage {
+    use slicing::packed_uint::PackedUintStorage;
+    use slicing::slices::{ByteSlice, SliceBackedChoice, SliceDefault, SliceRead, SliceWrite};
+
+    /// Value at `index` of a (pseudo-random) pattern, within `bits` bits.
+    fn pattern(index: usize, bits: usize) -> u32 {
+        ((index * 2_654_435_761) as u32) & ((1u64 << bits) - 1) as u32
+    }
+
+    pub fn array_get_set() {
+        // 3 bits per value: values cross byte boundaries.
+        let mut storage = PackedUintStorage::<u8, 3, 11>::from_iter_to_array(
+            (0..11).map(|i| pattern(i, 3) as u8),
+        );
+        assert_eq!(storage.len(), 11);
+        assert_eq!(storage.as_bytes().len(), 5);
+        for i in 0..11 {
+            assert_eq!(storage.get(i), pattern(i, 3) as u8);
+        }
+        assert!(storage.check_and_set(2, &7));
+        assert!(!storage.check_and_set(2, &7));
+        storage.set(3, &0);
+        assert_eq!(storage.get(1), pattern(1, 3) as u8);
+        assert_eq!(storage.get(2), 7);
+        assert_eq!(storage.get(3), 0);
+        assert_eq!(storage.get(4), pattern(4, 3) as u8);
+
+        let storage = PackedUintStorage::<u16, 12, 9>::from_iter_to_array(
+            (0..9).map(|i| pattern(i, 12) as u16),
+        );
+        for (i, value) in storage.iter().enumerate() {
+            assert_eq!(*value, pattern(i, 12) as u16);
+        }
+        for (i, value) in storage.values().enumerate().rev() {
+            assert_eq!(value, pattern(i, 12) as u16);
+        }
+
+        let storage = PackedUintStorage::<u8, 5, 7>::from_value_to_array(&31);
+        assert!(storage.iter().all(|value| *value == 31));
+        let storage = PackedUintStorage::<u8, 5, 7>::from_default(7, SliceBackedChoice::Array);
+        assert!(storage.values().all(|value| value == 0));
+    }
+
+    /// `u32` values (more than 16 bits).
+    pub fn wide_values() {
+        let mut bytes = [0u8; 10];
+        let mut storage = PackedUintStorage::<u32, 20, 0>::from_byte_slice(
+            ByteSlice::from_mutable(&mut bytes),
+            4,
+        );
+        for i in 0..4 {
+            assert!(storage.set_value(i, pattern(i + 1, 20)));
+        }
+        for i in 0..4 {
+            assert_eq!(storage.get_value(i), pattern(i + 1, 20));
+        }
+        assert_eq!(storage.values().len(), 4);
+        assert_eq!(*storage.iter().nth(2).unwrap(), pattern(3, 20));
+        assert_eq!(SliceRead::get(&storage, 3), pattern(4, 20));
+        // Backed by `bytes`: values are least significant bit first.
+        drop(storage);
+        assert_eq!(bytes[0], pattern(1, 20) as u8);
+
+        let storage = PackedUintStorage::<u32, 32, 3>::from_value_to_array(&u32::MAX);
+        assert!(storage.iter().all(|value| *value == u32::MAX));
+    }
+
+    pub fn byte_slice_variants() {
+        let bytes = [0b1010_0101u8, 0b1111_0000];
+        let storage =
+            PackedUintStorage::<u8, 4, 0>::from_byte_slice(ByteSlice::from_shared(&bytes), 4);
+        let expected = [0b0101, 0b1010, 0b0000, 0b1111];
+        assert_eq!(storage.iter().count(), expected.len());
+        for (value, expected) in storage.iter().zip(expected) {
+            assert_eq!(*value, expected);
+        }
+
+        let storage =
+            PackedUintStorage::<u8, 4, 4>::from_byte_slice(ByteSlice::from_array(bytes), 4);
+        assert_eq!(storage.get(3), 0b1111);
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    pub fn vec_based() {
+        let mut storage =
+            PackedUintStorage::<u16, 9, 0>::from_iter_to_vec((0..30).map(|i| pattern(i, 9) as u16));
+        assert_eq!(storage.len(), 30);
+        assert_eq!(storage.as_bytes().len(), 34);
+        storage.set(29, &511);
+        assert_eq!(storage.get(28), pattern(28, 9) as u16);
+        assert_eq!(storage.get(29), 511);
+
+        let shared = [1u8, 2, 3];
+        let storage = PackedUintStorage::<u8, 2, 0>::from_shared(&shared);
+        assert_eq!(storage.len(), 3);
+        assert_eq!(storage.get(2), 3);
+
+        let narr = PackedUintStorage::<u8, 2, 3>::from_array([3, 0, 1]).to_non_array_vec_based();
+        let array = PackedUintStorage::<u8, 2, 3>::from_non_array_vec_based(
+            &narr,
+            &SliceBackedChoice::Array,
+        );
+        assert_eq!(array.get(0), 3);
+        assert_eq!(array.get(2), 1);
+    }
+}
+
+#[cfg(test)]
+mod uint_storage_tests {
+    #[test]
+    fn array_get_set() {
+        super::uint_storage::array_get_set();
+    }
+
+    #[test]
+    fn wide_values() {
+        super::uint_storage::wide_values();
+    }
+
+    #[test]
+    fn byte_slice_variants() {
+        super::uint_storage::byte_slice_variants();
+    }
+
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    #[test]
+    fn vec_based() {
+        super::uint_storage::vec_based();
+    }
+}
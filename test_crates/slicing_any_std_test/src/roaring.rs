@@ -0,0 +1,161 @@
+/// Helpers for compressed (Roaring-style) bool storage. Used both by tests in this project, and by tests in `ok_std/` and `no_std_heap/` projects.
+pub mod bool_storage {
+    use slicing::roaring::{RoaringBoolStorage, CHUNK_BITS};
+    use slicing::slices::{SliceBackedChoice, SliceDefault, SliceRead, SliceWrite};
+    use slicing::Error;
+
+    /// Bit at `index` of a (pseudo-random) pattern.
+    fn pattern(index: usize) -> bool {
+        index % 3 == 0 || index % 7 == 1
+    }
+
+    pub fn get_set_and_iter() {
+        let mut storage = RoaringBoolStorage::<100>::from_iter_to_array((0..100).map(pattern));
+        assert_eq!(storage.len(), 100);
+        for i in 0..100 {
+            assert_eq!(storage.get(i), pattern(i));
+        }
+        assert!(storage.check_and_set(2, &true));
+        assert!(!storage.check_and_set(2, &true));
+        storage.set(0, &false);
+        assert!(storage.get(2) && !storage.get(0));
+        for (i, bit) in storage.iter().enumerate() {
+            assert_eq!(*bit, i == 2 || (i != 0 && pattern(i)));
+        }
+        assert_eq!(storage.iter().len(), 100);
+        assert_eq!(
+            storage.count_ones(),
+            storage.iter().filter(|bit| **bit).count()
+        );
+
+        let storage = RoaringBoolStorage::<70>::from_value_to_array(&true);
+        assert_eq!(storage.count_ones(), 70);
+        let storage = RoaringBoolStorage::<70>::from_default(70, SliceBackedChoice::Array);
+        assert_eq!(storage.count_zeros(), 70);
+    }
+
+    /// Sparse and clustered bits across the whole `u32` space.
+    pub fn sparse_and_clustered() {
+        const BITS: usize = 1 << 32;
+        let mut sparse = RoaringBoolStorage::<0>::from_default_to_vec(BITS);
+        let sparse_indices = (0..1000).map(|i| i * 4_294_967 + 13);
+        for index in sparse_indices.clone() {
+            sparse.set(index, &true);
+        }
+        assert_eq!(sparse.count_ones(), 1000);
+        assert!(sparse.iter_ones().eq(sparse_indices));
+        assert!(sparse.heap_size() < 4000);
+
+        // 100K set bits in one cluster (spanning chunks).
+        let mut clustered = RoaringBoolStorage::<0>::from_default_to_vec(BITS);
+        for index in 3 * CHUNK_BITS - 50_000..3 * CHUNK_BITS + 50_000 {
+            clustered.set(index, &true);
+        }
+        assert_eq!(clustered.count_ones(), 100_000);
+        // Switched from array to bitmap containers...
+        assert!(clustered.heap_size() <= 2 * 8192);
+        // ... and to run containers.
+        clustered.run_optimize();
+        assert!(clustered.heap_size() <= 8);
+        assert!(clustered.get(3 * CHUNK_BITS) && !clustered.get(3 * CHUNK_BITS + 50_000));
+
+        // Split a run, and switch back to an array container when removing.
+        clustered.set(3 * CHUNK_BITS, &false);
+        assert!(!clustered.get(3 * CHUNK_BITS));
+        assert!(clustered.get(3 * CHUNK_BITS + 1));
+        for index in 3 * CHUNK_BITS..3 * CHUNK_BITS + 50_000 {
+            clustered.set(index, &false);
+        }
+        assert_eq!(clustered.count_ones(), 50_000);
+        assert_eq!(clustered.iter_ones().next(), Some(3 * CHUNK_BITS - 50_000));
+    }
+
+    pub fn union_and_intersection() {
+        const BITS: usize = 4 * CHUNK_BITS;
+        let left = RoaringBoolStorage::<0>::from_iter_to_vec((0..BITS).map(|i| i % 5 == 0));
+        let right = RoaringBoolStorage::<0>::from_iter_to_vec(
+            (0..BITS).map(|i| i % 3 == 0 || (i > 70_000 && i < 140_000)),
+        );
+        let sparse = RoaringBoolStorage::<0>::from_iter_to_vec((0..BITS).map(|i| i % 1000 == 0));
+
+        let and = &left & &right;
+        let or = &left | &right;
+        for i in (0..BITS).step_by(7) {
+            let (l, r) = (left.get(i), right.get(i));
+            assert_eq!(and.get(i), l && r, "{i}");
+            assert_eq!(or.get(i), l || r, "{i}");
+        }
+        let expected_and = (0..BITS).filter(|i| left.get(*i) && right.get(*i)).count();
+        assert_eq!(and.count_ones(), expected_and);
+        assert_eq!(
+            or.count_ones(),
+            left.count_ones() + right.count_ones() - expected_and
+        );
+
+        let mut sparse_and = sparse.clone();
+        sparse_and.and_assign(&right);
+        assert!(sparse_and
+            .iter_ones()
+            .eq((0..BITS).filter(|i| i % 1000 == 0 && right.get(*i))));
+        let mut sparse_or = sparse.clone();
+        sparse_or.or_assign(&sparse);
+        assert_eq!(sparse_or.count_ones(), sparse.count_ones());
+
+        // 2000 runs each, but their intersection splits every run in two.
+        let mut left =
+            RoaringBoolStorage::<0>::from_iter_to_vec((0..BITS).map(|i| i < 8000 && i % 4 != 3));
+        let mut right = RoaringBoolStorage::<0>::from_iter_to_vec(
+            (0..BITS).map(|i| (2..8002).contains(&i) && i % 4 != 1),
+        );
+        left.run_optimize();
+        right.run_optimize();
+        assert_eq!(left.heap_size(), 8000);
+        let and = &left & &right;
+        assert_eq!(and.count_ones(), 3999);
+        assert!(and.iter_ones().eq((2..8000).step_by(2)));
+        // Not 3999 runs (15996 bytes), but an array container.
+        assert_eq!(and.heap_size(), 7998);
+    }
+
+    pub fn conversions() {
+        let mut items = [true, false, false, true, true, false, true];
+        let storage = RoaringBoolStorage::<7>::from_shared(&items);
+        assert!(storage.iter().eq(items.iter()));
+        // It would compress a copy, so writes wouldn't reach `items`.
+        assert_eq!(
+            RoaringBoolStorage::<7>::try_from_mutable(&mut items).err(),
+            Some(Error::NotSliceBacked)
+        );
+        let storage = RoaringBoolStorage::<7>::from_array(items);
+        let narr = storage.to_non_array_vec_based();
+        assert_eq!(narr.len(), 7);
+        let storage =
+            RoaringBoolStorage::<7>::from_non_array_vec_based(&narr, &SliceBackedChoice::Array);
+        assert!(storage.iter().eq(items.iter()));
+        let storage = RoaringBoolStorage::<0>::from_vec(items.to_vec());
+        assert_eq!(storage.count_ones(), 4);
+    }
+}
+
+#[cfg(test)]
+mod bool_storage_tests {
+    #[test]
+    fn get_set_and_iter() {
+        super::bool_storage::get_set_and_iter();
+    }
+
+    #[test]
+    fn sparse_and_clustered() {
+        super::bool_storage::sparse_and_clustered();
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        super::bool_storage::union_and_intersection();
+    }
+
+    #[test]
+    fn conversions() {
+        super::bool_storage::conversions();
+    }
+}
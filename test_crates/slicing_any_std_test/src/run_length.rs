@@ -0,0 +1,123 @@
+/// Helpers for run-length encoded storage. Used both by tests in this project, and by tests in `ok_std/` and `no_std_heap/` projects.
+pub mod storage {
+    use slicing::run_length::RunLengthStorage;
+    use slicing::slices::{SliceBackedChoice, SliceRead, SliceWrite};
+
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::vec::Vec;
+
+    pub fn get_and_iter() {
+        use slicing::slices::SliceDefault;
+
+        let items = [1u8, 1, 1, 2, 2, 3, 1, 1, 1, 1];
+        let storage = RunLengthStorage::<u8, 10>::from_iter_to_array(items.iter().cloned());
+        assert_eq!(storage.len(), 10);
+        assert_eq!(storage.num_runs(), 4);
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(storage.get(i), *item);
+        }
+        assert!(storage.iter().eq(items.iter()));
+        assert!(storage.iter().rev().eq(items.iter().rev()));
+        assert_eq!(storage.iter().nth(5), Some(&3));
+        assert!(storage
+            .runs()
+            .map(|(range, _)| range)
+            .eq([0..3, 3..5, 5..6, 6..10]));
+
+        let mut iter = storage.iter();
+        assert_eq!(iter.next_back(), Some(&1));
+        assert_eq!(iter.nth(4), Some(&2));
+        assert_eq!(iter.len(), 4);
+
+        let storage = RunLengthStorage::<u16, 1000>::from_default_to_array();
+        assert_eq!(storage.num_runs(), 1);
+        assert_eq!(storage.get(999), 0);
+    }
+
+    /// Compare `set` against a plain `Vec`, including splits and merges.
+    pub fn set_splits_and_merges() {
+        use slicing::slices::SliceDefault;
+
+        let mut expected = Vec::from([5u8; 40]);
+        let mut storage = RunLengthStorage::<u8, 0>::from_vec(expected.clone());
+        let changes = [
+            (10, 7), // split in the middle
+            (10, 5), // merge back
+            (0, 7),  // first item of a run
+            (1, 7),  // merge with the previous run
+            (39, 6), // last item of a run
+            (38, 6), // merge with the next run
+            (20, 7),
+            (22, 7),
+            (21, 7), // merge with both neighbors
+            (21, 7), // no change
+            (2, 7),
+        ];
+        for (index, value) in changes {
+            let changed = expected[index] != value;
+            expected[index] = value;
+            assert_eq!(storage.check_and_set(index, &value), changed);
+            assert!(storage.iter().eq(expected.iter()));
+        }
+        // Neighboring runs always differ.
+        let values = storage.runs().map(|(_, value)| *value).collect::<Vec<_>>();
+        assert!(values.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_eq!(storage.num_runs(), 5);
+    }
+
+    /// `RunLengthStorage` implements `Slice`, too.
+    pub fn slice_storage_conversions() {
+        use slicing::slices::{Slice, SliceBacked, SliceStorage};
+        use slicing::Error;
+
+        let items = [0u32, 0, 0, 9, 9, 0];
+        let slice_storage = SliceStorage::<u32, 6>::from_array(items);
+        let storage = RunLengthStorage::<u32, 6>::from_slice_storage(&slice_storage);
+        assert_eq!(storage.num_runs(), 3);
+
+        let array_based = storage.to_slice_storage(SliceBackedChoice::Array).unwrap();
+        assert_eq!(array_based.shared_slice(), &items);
+        let vec_based = storage.to_slice_storage(SliceBackedChoice::Vec).unwrap();
+        assert_eq!(vec_based.shared_slice(), &items);
+        assert_eq!(
+            storage.to_slice_storage(SliceBackedChoice::Shared).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Shared))
+        );
+        let shorter = RunLengthStorage::<u32, 6>::from_vec(items[1..].to_vec());
+        assert_eq!(
+            shorter.to_slice_storage(SliceBackedChoice::Array).err(),
+            Some(Error::LengthMismatch {
+                expected: 6,
+                actual: 5
+            })
+        );
+
+        let mut timeline = RunLengthStorage::<u32, 0>::new();
+        timeline.push_run(1, 1_000_000);
+        timeline.push_run(2, 1_000_000);
+        timeline.push_run(2, 5);
+        assert_eq!(timeline.len(), 2_000_005);
+        assert_eq!(timeline.num_runs(), 2);
+        assert_eq!(timeline.get(1_000_000), 2);
+    }
+}
+
+#[cfg(test)]
+mod storage_tests {
+    #[test]
+    fn get_and_iter() {
+        super::storage::get_and_iter();
+    }
+
+    #[test]
+    fn set_splits_and_merges() {
+        super::storage::set_splits_and_merges();
+    }
+
+    #[test]
+    fn slice_storage_conversions() {
+        super::storage::slice_storage_conversions();
+    }
+}
@@ -0,0 +1,114 @@
+/// Helpers for SIMD-accelerated bulk operations. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod bulk_ops {
+    use slicing::byte_slice::ByteSliceBoolStorage;
+    use slicing::packed_bool::PackedBoolStorage;
+    use slicing::simd::BitwiseOp;
+    use slicing::slices::{ByteSlice, Slice, SliceDefault, SliceStorage, SliceWrite};
+
+    /// Lengths cover whole vectors plus a scalar tail, so both paths run.
+    pub fn fill_find_and_count() {
+        let mut bytes = ByteSlice::<200>::from_default_to_array();
+        bytes.fill(7);
+        assert_eq!(bytes.count_matching(7), 200);
+        assert_eq!(bytes.find_first(9), None);
+        bytes.set(130, &9);
+        bytes.set(199, &9);
+        assert_eq!(bytes.find_first(9), Some(130));
+        assert_eq!(bytes.count_matching(9), 2);
+        assert_eq!(bytes.count_matching(7), 198);
+
+        let mut words = SliceStorage::<u32, 37>::from_value_to_array(&1);
+        words.set(36, &5);
+        assert_eq!(words.find_first(5), Some(36));
+        assert_eq!(words.count_matching(1), 36);
+
+        let floats = SliceStorage::<f64, 11>::from_iter_to_array((0..11).map(|i| i as f64));
+        assert_eq!(floats.find_first(10.0), Some(10));
+        // Like `PartialEq`: NaN doesn't match anything.
+        let nans = [f64::NAN; 11];
+        assert!(!SliceStorage::<f64, 11>::from_array(nans).items_eq(&nans));
+    }
+
+    pub fn compare_and_bitwise() {
+        let left = ByteSlice::<100>::from_iter_to_array((0..100).map(|i| i as u8));
+        let same: [u8; 100] = core::array::from_fn(|i| i as u8);
+        assert!(left.items_eq(&same));
+        let mut different = same;
+        different[99] = 0;
+        assert!(!left.items_eq(&different));
+        assert!(!left.items_eq(&same[..99]));
+
+        let mut bytes = ByteSlice::<100>::from_array(same);
+        bytes.bitwise_assign(&[0x0F; 100], BitwiseOp::And);
+        assert_eq!(
+            bytes.count_ones(),
+            (0..100)
+                .map(|i: u32| (i & 0x0F).count_ones() as usize)
+                .sum()
+        );
+        bytes.bitwise_assign(&[0xFF; 100], BitwiseOp::AndNot);
+        assert_eq!(bytes.count_matching(0), 100);
+        bytes.bitwise_assign(&[0x81; 100], BitwiseOp::Or);
+        bytes.bitwise_assign(&[0x01; 100], BitwiseOp::Xor);
+        assert_eq!(bytes.count_matching(0x80), 100);
+
+        let words = SliceStorage::<u64, 19>::from_value_to_array(&u64::MAX);
+        assert_eq!(words.count_ones(), 19 * 64);
+    }
+
+    pub fn packed_bool_fill_and_eq() {
+        let mut storage = PackedBoolStorage::<u8, 1001>::from_value_to_array(&false);
+        storage.fill(true);
+        assert_eq!(storage.count_ones(), 1001);
+        storage.set(1000, &false);
+        let other = PackedBoolStorage::<u8, 1001>::from_iter_to_array((0..1001).map(|i| i != 1000));
+        assert!(storage.bits_eq(&other));
+        storage.not_assign();
+        assert_eq!(storage.count_ones(), 1);
+        assert!(!storage.bits_eq(&other));
+        storage.or_assign(&other);
+        assert_eq!(storage.count_ones(), 1001);
+    }
+
+    /// Whole vectors of blocks get skipped; the rest is searched per bit.
+    pub fn packed_bool_find_and_count() {
+        let mut storage = ByteSliceBoolStorage::<1001>::from_value_to_array(&false);
+        assert_eq!(storage.find_first(true), None);
+        assert_eq!(storage.find_first(false), Some(0));
+        storage.set(900, &true);
+        storage.set(1000, &true);
+        assert_eq!(storage.find_first(true), Some(900));
+        assert_eq!(storage.count_matching(true), 2);
+        assert_eq!(storage.count_matching(false), 999);
+
+        let ones = ByteSliceBoolStorage::<1001>::from_value_to_array(&true);
+        storage.bitwise_assign(&ones, BitwiseOp::Xor);
+        assert_eq!(storage.find_first(false), Some(900));
+        assert_eq!(storage.count_matching(false), 2);
+        storage.bitwise_assign(&ones, BitwiseOp::AndNot);
+        assert_eq!(storage.find_first(true), None);
+    }
+}
+
+#[cfg(test)]
+mod bulk_ops_tests {
+    #[test]
+    fn fill_find_and_count() {
+        super::bulk_ops::fill_find_and_count();
+    }
+
+    #[test]
+    fn compare_and_bitwise() {
+        super::bulk_ops::compare_and_bitwise();
+    }
+
+    #[test]
+    fn packed_bool_fill_and_eq() {
+        super::bulk_ops::packed_bool_fill_and_eq();
+    }
+
+    #[test]
+    fn packed_bool_find_and_count() {
+        super::bulk_ops::packed_bool_find_and_count();
+    }
+}
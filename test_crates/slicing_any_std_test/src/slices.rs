@@ -1,8 +1,15 @@
 /// Helpers for (unpacked) bool slice. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
 pub mod bool_slice {
+    use slicing::packed_bool::PackedBoolStorage;
     use slicing::slices::BoolSlice;
+    use slicing::slices::SliceBacked;
     use slicing::slices::SliceDefault;
+    use slicing::slices::SliceIterMut;
+    use slicing::slices::SliceRead;
     use slicing::slices::SliceStorageDefault;
+    use slicing::slices::SliceWrite;
+    #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+    use slicing::slices::SliceVec;
     // If this module were in the file of its parent module, do NOT have the
     // following three at the parent module's level. Otherwise macro vec![]
     // would not resolve! (Even more confusing: If you did have `use
@@ -49,6 +56,89 @@ pub mod bool_slice {
         assert_equal_items(&BoolSlice::<1>::from_default_to_array(), &[false]);
     }
 
+    /// Read-only code requires `SliceRead` only.
+    fn count_true<S: SliceRead<bool>>(slice: &S) -> usize {
+        slice.iter().filter(|item| **item).count()
+    }
+
+    /// Code that modifies items requires `SliceWrite`.
+    fn flip_all<S: SliceWrite<bool>>(slice: &mut S) {
+        for i in 0..slice.len() {
+            let value = !slice.get(i);
+            slice.set(i, &value);
+        }
+    }
+
+    pub fn read_and_write_capabilities() {
+        let mut unpacked = BoolSlice::<3>::from_array([true, false, false]);
+        assert_eq!(count_true(&unpacked), 1);
+        flip_all(&mut unpacked);
+        assert_equal_items(&unpacked, &[false, true, true]);
+
+        let mut packed = PackedBoolStorage::<u8, 3>::from_array([true, true, false]);
+        assert_eq!(count_true(&packed), 2);
+        flip_all(&mut packed);
+        assert_eq!(count_true(&packed), 1);
+        assert!(packed.get(2));
+    }
+
+    /// Bulk transforms require `SliceIterMut`, whether items are packed or not.
+    fn negate_all<S: SliceIterMut<bool>>(slice: &mut S) {
+        for mut item in slice.iter_mut() {
+            *item = !*item;
+        }
+    }
+
+    pub fn iter_mut() {
+        let mut unpacked = BoolSlice::<3>::from_array([true, false, false]);
+        negate_all(&mut unpacked);
+        assert_equal_items(&unpacked, &[false, true, true]);
+
+        let mut array = [true, true];
+        let mut mutable = BoolSlice::<0>::from_mutable(&mut array);
+        negate_all(&mut mutable);
+        assert_equal_items(&mutable, &[false, false]);
+
+        let shared = [true];
+        let mut shared = BoolSlice::<0>::from_shared(&shared);
+        assert_eq!(
+            shared.try_iter_mut().err(),
+            Some(slicing::Error::SharedNotMutable)
+        );
+
+        // Proxies of bits in the same block, alive at the same time.
+        let mut packed = PackedBoolStorage::<u8, 10>::from_array([
+            true, false, true, false, false, false, false, false, true, true,
+        ]);
+        let mut bits = packed.iter_mut();
+        let mut first = bits.next().unwrap();
+        let mut last = bits.next_back().unwrap();
+        let mut second = bits.next().unwrap();
+        assert_eq!(bits.len(), 7);
+        *first = false;
+        *second = true;
+        *last = false;
+        drop((second, first, last));
+        drop(bits);
+        assert!(!packed.get(0));
+        assert!(packed.get(1));
+        assert!(packed.get(2));
+        assert!(!packed.get(9));
+
+        negate_all(&mut packed);
+        assert_eq!(count_true(&packed), 7);
+        assert!(packed.get(0));
+        assert!(!packed.get(1));
+        assert!(packed.get(9));
+
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        {
+            let mut vec = BoolSlice::<0>::from_vec(vec![false, true]);
+            negate_all(&mut vec);
+            assert_equal_items(&vec, &[true, false]);
+        }
+    }
+
     #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
     pub fn from_vec_etc() {
         // Test that `SliceStorage::from_vec_new()` is empty, regardless of const generic param N.
@@ -64,6 +154,475 @@ pub mod bool_slice {
     }
 }
 
+/// Helpers for standard trait implementations of `SliceStorage*`. Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod std_traits {
+    use slicing::slices::{ByteSlice, Slice, SliceDefault, SliceStorage};
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::vec;
+
+    pub fn index_deref_and_iterate() {
+        let mut bytes = ByteSlice::<4>::from_array([1, 2, 3, 4]);
+        assert_eq!(bytes[1], 2);
+        assert_eq!(&bytes[1..3], &[2, 3]);
+        bytes[0] = 9;
+        bytes.as_mut()[3] = 8;
+        assert_eq!(bytes.as_ref(), &[9, 2, 3, 8]);
+        // Slice methods through `Deref`.
+        assert_eq!(bytes.first(), Some(&9));
+        assert!(bytes.contains(&3));
+
+        for item in &mut bytes {
+            *item += 1;
+        }
+        let mut sum = 0;
+        for item in &bytes {
+            sum += *item;
+        }
+        assert_eq!(sum, 10 + 3 + 4 + 9);
+
+        let mut owned = bytes.into_iter();
+        assert_eq!(owned.len(), 4);
+        assert_eq!(owned.next_back(), Some(9));
+        assert_eq!(owned.next(), Some(10));
+
+        let items = [5u8, 6];
+        let shared = ByteSlice::<0>::from_shared(&items);
+        assert!(shared.into_iter().eq([5, 6]));
+    }
+
+    pub fn content_based_comparison() {
+        let items = [1u16, 2, 3];
+        let array = SliceStorage::<u16, 3>::from_array(items);
+        let shared = SliceStorage::<u16, 0>::from_shared(&items);
+        assert_eq!(array, shared);
+        assert!(SliceStorage::<u16, 0>::from_shared(&items[..2]) < array);
+        assert!(array != SliceStorage::<u16, 3>::from_array([1, 2, 4]));
+        assert!(array < SliceStorage::<u16, 3>::from_array([1, 2, 4]));
+
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        {
+            use slicing::slices::{SliceClone, SliceStorageClone};
+
+            let vec = SliceStorage::<u16, 3>::from_vec(vec![1, 2, 3]);
+            assert_eq!(array, vec);
+            assert_eq!(array.cmp(&vec), core::cmp::Ordering::Equal);
+
+            // `Clone` flavour: owning iteration moves out of `Vec`.
+            let strings = SliceStorageClone::<&str, 0>::from_vec(vec!["a", "b"]);
+            assert!(strings.into_iter().eq(["a", "b"]));
+        }
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    pub fn hash_as_key() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        set.insert(SliceStorage::<u8, 2>::from_array([1, 2]));
+        assert!(set.contains(&SliceStorage::<u8, 2>::from_vec(vec![1, 2])));
+        assert!(!set.contains(&SliceStorage::<u8, 2>::from_array([2, 1])));
+    }
+}
+
+/// Helpers for sub-slice views (`SliceView` and `SliceViewMut`). Used both by tests in this project, and by tests in `ok_std/` and `no_std_*/` projects.
+pub mod views {
+    use slicing::slices::{
+        ByteSlice, SliceBacked, SliceDefault, SliceRead, SliceReadView, SliceView, SliceViewMut,
+        SliceWrite,
+    };
+    use slicing::Error;
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::vec;
+
+    /// A "subsystem" that works with any storage.
+    fn increment_all<S: SliceWrite<u8>>(slice: &mut S) {
+        for i in 0..slice.len() {
+            let value = slice.get(i) + 1;
+            slice.set(i, &value);
+        }
+    }
+
+    /// A read-only "subsystem".
+    fn sum_of_chunk_sums<S: SliceView<u8>>(slice: &S) -> u8 {
+        slice
+            .chunks(2)
+            .map(|chunk| (0..chunk.len()).map(|i| chunk.get(i)).sum::<u8>())
+            .sum()
+    }
+
+    pub fn view_and_view_mut() {
+        let mut bytes = ByteSlice::<5>::from_array([0, 1, 2, 3, 4]);
+        // Views are read-only (no `SliceWrite`), whatever the parent.
+        let view: SliceReadView<u8> = bytes.view(1..3);
+        assert_eq!(view.shared_slice(), &[1, 2]);
+        assert_eq!(view, SliceReadView::new(&[1, 2]));
+        assert_eq!(bytes.view(..).len(), 5);
+        assert_eq!(
+            bytes.try_view(3..6).err(),
+            Some(Error::RangeOutOfBounds {
+                start: 3,
+                end: 6,
+                len: 5
+            })
+        );
+
+        assert_eq!(sum_of_chunk_sums(&bytes), 10);
+
+        let mut view_mut: ByteSlice<0> = bytes.view_mut(3..=4);
+        assert!(matches!(view_mut, ByteSlice::Mutable(_)));
+        increment_all(&mut view_mut);
+        assert_eq!(bytes.shared_slice(), &[0, 1, 2, 4, 5]);
+
+        let items = [1u8, 2];
+        let mut shared = ByteSlice::<0>::from_shared(&items);
+        assert_eq!(shared.view(1..).shared_slice(), &[2]);
+        assert_eq!(shared.try_view_mut(..).err(), Some(Error::SharedNotMutable));
+    }
+
+    pub fn split_at_mut() {
+        let mut bytes = ByteSlice::<4>::from_array([0, 1, 2, 3]);
+        let (mut left, mut right) = bytes.split_at_mut(1);
+        increment_all(&mut left);
+        right.set(0, &9);
+        assert_eq!(bytes.shared_slice(), &[1, 9, 2, 3]);
+        assert_eq!(
+            bytes.try_split_at_mut(5).err(),
+            Some(Error::RangeOutOfBounds {
+                start: 0,
+                end: 5,
+                len: 4
+            })
+        );
+
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        {
+            let mut vec = vec![5u8, 6, 7];
+            let mut vec_ref = ByteSlice::<0>::from_vec_ref(&mut vec);
+            let (left, mut right) = vec_ref.split_at_mut(3);
+            assert!(left.len() == 3 && right.is_empty());
+            increment_all(&mut right);
+            increment_all(&mut vec_ref.view_mut(2..));
+            assert_eq!(vec, [5, 6, 8]);
+        }
+    }
+
+    pub fn chunks_and_windows() {
+        let mut bytes = ByteSlice::<5>::from_array([0; 5]);
+        let lengths = [2, 2, 1];
+        assert_eq!(bytes.chunks(2).len(), 3);
+        for (chunk, length) in bytes.chunks(2).zip(lengths) {
+            assert_eq!(chunk.len(), length);
+        }
+        for mut chunk in bytes.chunks_mut(2).rev().skip(1) {
+            increment_all(&mut chunk);
+        }
+        assert_eq!(bytes.shared_slice(), &[1, 1, 1, 1, 0]);
+
+        let windows = bytes.windows(4);
+        assert_eq!(windows.len(), 2);
+        for window in bytes.windows(4) {
+            assert_eq!(window.len(), 4);
+        }
+        assert_eq!(
+            bytes.windows(4).last().unwrap().shared_slice(),
+            &[1, 1, 1, 0]
+        );
+
+        #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+        {
+            let mut vec = ByteSlice::<0>::from_vec(vec![1, 2, 3]);
+            for mut chunk in vec.chunks_mut(2) {
+                chunk.set(0, &0);
+            }
+            assert_eq!(vec.shared_slice(), &[0, 2, 0]);
+        }
+
+        let items = [1u8, 2];
+        let mut shared = ByteSlice::<0>::from_shared(&items);
+        assert_eq!(
+            shared.try_chunks_mut(1).err(),
+            Some(Error::SharedNotMutable)
+        );
+    }
+}
+
+/// Helpers for Boxed, Rc and Arc variants. Used both by tests in this project, and by tests in `ok_std/` and `no_std_heap/` projects.
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+pub mod shared_ownership {
+    use slicing::packed_bool::PackedBoolStorage;
+    use slicing::run_length::RunLengthStorage;
+    use slicing::slices::{
+        ByteSlice, CollectTo, CollectToClone, Slice, SliceBacked, SliceBackedChoice, SliceClone,
+        SliceDefault, SliceRead, SliceStorage, SliceStorageClone, SliceVec, SliceWrite,
+    };
+    use slicing::Error;
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::{boxed::Box, rc::Rc, string::String, sync::Arc, vec};
+    #[cfg(not(feature = "no_std"))]
+    use std::{rc::Rc, sync::Arc};
+
+    pub fn boxed() {
+        let mut boxed = SliceStorage::<u8, 0>::Boxed(Box::new([1, 2, 3]));
+        assert_eq!(boxed.len(), 3);
+        boxed.set(0, &9);
+        assert_eq!(boxed.shared_slice(), &[9, 2, 3]);
+        assert_eq!(boxed.try_push(4), Err(Error::NotVecBased));
+        let copy = boxed.clone();
+        boxed.set(1, &0);
+        assert_eq!(copy.shared_slice(), &[9, 2, 3]);
+        assert!(boxed.into_iter().eq([9, 0, 3]));
+        assert!(SliceBackedChoice::Boxed.is_owned());
+    }
+
+    pub fn rc_and_arc() {
+        let rc: Rc<[u16]> = Rc::from([1, 2]);
+        let mut first = SliceStorage::<u16, 0>::Rc(rc);
+        // Unique, hence modifiable.
+        first.set(0, &5);
+        let mut second = first.clone();
+        assert_eq!(second, first);
+        if let (SliceStorage::Rc(a), SliceStorage::Rc(b)) = (&first, &second) {
+            assert!(Rc::ptr_eq(a, b));
+        }
+        assert_eq!(second.try_set(0, &6), Err(Error::SharedNotMutable));
+        drop(first);
+        second.set(0, &6);
+        assert_eq!(second.shared_slice(), &[6, 2]);
+
+        let strings: Arc<[String]> = vec![String::from("a"), String::from("b")].into();
+        let shared = SliceStorageClone::<String, 0>::Arc(strings);
+        let copy = shared.clone();
+        assert!(copy.into_iter().eq(["a", "b"]));
+        let vec_based = shared.to_vec_based();
+        assert!(matches!(vec_based, SliceStorageClone::Vec(_)));
+        assert_eq!(vec_based.len(), 2);
+    }
+
+    /// Boxed, Rc and Arc choices work like Vec: in constructors, and in
+    /// conversions from non-array (Vec-based) instances.
+    pub fn constructors_and_conversions() {
+        let rc = SliceStorage::<u8, 0>::from_rc(Rc::from([1, 2]));
+        assert!(matches!(rc, SliceStorage::Rc(_)));
+        let arc = ByteSlice::<0>::from_arc(Arc::from([1, 2]));
+        assert!(matches!(arc, ByteSlice::Arc(_)));
+        assert_eq!(arc.shared_slice(), rc.shared_slice());
+
+        let boxed = ByteSlice::<0>::from_default(2, SliceBackedChoice::Boxed);
+        assert!(matches!(boxed, ByteSlice::Boxed(_)));
+        assert_eq!(boxed.shared_slice(), &[0, 0]);
+        assert_eq!(
+            ByteSlice::<0>::try_from_default(1, SliceBackedChoice::Arc)
+                .unwrap()
+                .backed_choice(),
+            SliceBackedChoice::Arc
+        );
+        assert_eq!(
+            ByteSlice::<0>::from_value(&7, 2, SliceBackedChoice::Rc).shared_slice(),
+            &[7, 7]
+        );
+        assert_eq!(
+            ByteSlice::<0>::from_iter_to(1..3, 2, SliceBackedChoice::Boxed).backed_choice(),
+            SliceBackedChoice::Boxed
+        );
+
+        let narr = ByteSlice::<0>::from_vec(vec![1, 2]);
+        for choice in [
+            SliceBackedChoice::Boxed,
+            SliceBackedChoice::Rc,
+            SliceBackedChoice::Arc,
+        ] {
+            let converted = ByteSlice::<2>::try_from_non_array_vec_based(&narr, &choice).unwrap();
+            assert_eq!(converted.backed_choice(), choice);
+            assert_eq!(converted.shared_slice(), &[1, 2]);
+        }
+
+        // Packed storages aren't slice-backed, so they treat these like Vec.
+        let bools = PackedBoolStorage::<u8, 0>::from_default(10, SliceBackedChoice::Rc);
+        assert_eq!(bools.len(), 10);
+        let narr = PackedBoolStorage::<u8, 0>::from_boxed(Box::new([true; 10]));
+        assert_eq!(narr.len(), 10);
+        assert!(
+            PackedBoolStorage::<u8, 10>::from_non_array_vec_based(&narr, &SliceBackedChoice::Arc)
+                .get(9)
+        );
+        type Runs<const N: usize> = RunLengthStorage<u8, N>;
+        let runs = <Runs<0> as SliceDefault<u8, 0>>::from_arc(Arc::from([4, 4, 5]));
+        assert_eq!(runs.len(), 3);
+        assert_eq!(
+            <Runs<3> as SliceDefault<u8, 3>>::from_non_array_vec_based(
+                &runs,
+                &SliceBackedChoice::Rc
+            )
+            .get(2),
+            5
+        );
+        assert_eq!(
+            <Runs<3> as SliceDefault<u8, 3>>::try_from_non_array_vec_based(
+                &runs,
+                &SliceBackedChoice::VecRef
+            )
+            .err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::VecRef))
+        );
+
+        // Borrowing choices are errors, not `unimplemented!`.
+        let narr = ByteSlice::<0>::from_vec(vec![1, 2]);
+        assert_eq!(
+            ByteSlice::<2>::try_from_non_array_vec_based(&narr, &SliceBackedChoice::Shared).err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Shared))
+        );
+        let narr = PackedBoolStorage::<u8, 0>::from_boxed(Box::new([true; 3]));
+        assert_eq!(
+            PackedBoolStorage::<u8, 3>::try_from_non_array_vec_based(
+                &narr,
+                &SliceBackedChoice::Mutable
+            )
+            .err(),
+            Some(Error::UnsupportedChoice(SliceBackedChoice::Mutable))
+        );
+        assert_eq!(
+            PackedBoolStorage::<u8, 4>::try_from_non_array_vec_based(
+                &narr,
+                &SliceBackedChoice::Array
+            )
+            .err(),
+            Some(Error::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    /// Collecting, packing, unpacking and expanding into Boxed, Rc and Arc.
+    pub fn collect_pack_and_expand() {
+        let choices = [
+            SliceBackedChoice::Boxed,
+            SliceBackedChoice::Rc,
+            SliceBackedChoice::Arc,
+        ];
+        for choice in choices {
+            let collected: SliceStorage<u8, 0> = (1..4).collect_to(choice);
+            assert_eq!(collected.backed_choice(), choice);
+            assert_eq!(collected.shared_slice(), &[1, 2, 3]);
+            let strings: SliceStorageClone<String, 0> = ["a", "b"]
+                .iter()
+                .map(|s| String::from(*s))
+                .collect_to_clone(choice);
+            assert_eq!(strings.backed_choice(), choice);
+            assert_eq!(strings.len(), 2);
+
+            let packed =
+                PackedBoolStorage::<u8, 0>::try_pack(&[true, false, true], choice).unwrap();
+            assert_eq!(packed.len(), 3);
+            assert!(packed.get(2));
+            let unpacked = packed.unpack(choice);
+            assert_eq!(unpacked.backed_choice(), choice);
+            assert_eq!(unpacked.shared_slice(), &[true, false, true]);
+
+            let runs = RunLengthStorage::<u8, 0>::from_slice_storage(
+                &SliceStorage::<u8, 3>::from_array([4, 4, 5]),
+            );
+            let expanded = runs.to_slice_storage(choice).unwrap();
+            assert_eq!(expanded.backed_choice(), choice);
+            assert_eq!(expanded.shared_slice(), &[4, 4, 5]);
+        }
+    }
+
+    /// Cloning a shared slice-backed instance borrows the same slice again.
+    pub fn clone_shared() {
+        let items = [1u8, 2, 3];
+        let shared = ByteSlice::<0>::from_shared(&items);
+        let copy = shared.clone();
+        assert!(matches!(copy, ByteSlice::Shared(_)));
+        assert!(core::ptr::eq(copy.shared_slice(), &items[..]));
+        let strings = [String::from("a")];
+        let shared = SliceStorageClone::<String, 0>::from_shared(&strings);
+        assert!(matches!(shared.clone(), SliceStorageClone::Shared(_)));
+    }
+}
+
+/// Helpers for growable operations (`SliceVec`). Used both by tests in this project, and by tests in `ok_std/` and `no_std_heap/` projects.
+#[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
+pub mod growable {
+    use slicing::slices::{ByteSlice, SliceBacked, SliceDefault, SliceRead, SliceVec};
+    use slicing::Error;
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::vec;
+
+    /// Generic code can grow a buffer without matching on variants.
+    fn append_ramp<S: SliceVec<u8>>(slice: &mut S, count: u8) {
+        slice.reserve(count as usize);
+        for item in 0..count {
+            slice.push(item);
+        }
+    }
+
+    pub fn push_pop_insert_remove() {
+        let mut bytes = ByteSlice::<0>::from_vec(vec![]);
+        append_ramp(&mut bytes, 3);
+        assert_eq!(bytes.shared_slice(), &[0, 1, 2]);
+        assert_eq!(bytes.pop(), Some(2));
+        bytes.insert(0, 7);
+        bytes.insert(bytes.len(), 8);
+        assert_eq!(bytes.shared_slice(), &[7, 0, 1, 8]);
+        assert_eq!(bytes.remove(1), 0);
+        assert_eq!(
+            bytes.try_remove(3),
+            Err(Error::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(
+            bytes.try_insert(4, 0),
+            Err(Error::IndexOutOfBounds { index: 4, len: 3 })
+        );
+
+        let mut vec = vec![1u8, 2];
+        let mut vec_ref = ByteSlice::<0>::from_vec_ref(&mut vec);
+        append_ramp(&mut vec_ref, 2);
+        drop(vec_ref);
+        assert_eq!(vec, [1, 2, 0, 1]);
+    }
+
+    pub fn resize_truncate_extend() {
+        let mut bytes = ByteSlice::<0>::from_vec_with_capacity(8);
+        bytes.extend_from_slice(&[1, 2, 3]);
+        bytes.resize(5, 9);
+        assert_eq!(bytes.shared_slice(), &[1, 2, 3, 9, 9]);
+        bytes.truncate(2);
+        bytes.shrink_to_fit();
+        assert_eq!(bytes.shared_slice(), &[1, 2]);
+        assert!(bytes.mutable_vec().capacity() < 8);
+    }
+
+    pub fn fixed_size_errors_or_promotes() {
+        let mut array = ByteSlice::<2>::from_array([1, 2]);
+        assert_eq!(array.try_push(3), Err(Error::NotVecBased));
+        assert_eq!(array.try_pop(), Err(Error::NotVecBased));
+        assert_eq!(array.try_resize(4, 0), Err(Error::NotVecBased));
+        assert_eq!(array.try_extend_from_slice(&[3]), Err(Error::NotVecBased));
+        assert_eq!(array.shared_slice(), &[1, 2]);
+
+        array.promote_to_vec().push(3);
+        append_ramp(&mut array, 1);
+        assert_eq!(array.shared_slice(), &[1, 2, 3, 0]);
+
+        let items = [5u8, 6];
+        let mut shared = ByteSlice::<0>::from_shared(&items);
+        assert_eq!(shared.try_truncate(1), Err(Error::NotVecBased));
+        shared.promote_to_vec();
+        shared.truncate(1);
+        assert_eq!(shared.shared_slice(), &[5]);
+        assert_eq!(items, [5, 6]);
+    }
+}
+
 #[cfg(test)]
 mod bool_slice_tests {
     #[test]
@@ -76,6 +635,11 @@ mod bool_slice_tests {
         super::bool_slice::new_contains_initial_false();
     }
 
+    #[test]
+    fn iter_mut() {
+        super::bool_slice::iter_mut();
+    }
+
     #[cfg(any(not(feature = "no_std"), feature = "no_std_heap"))]
     #[test]
     pub fn from_vec_etc() {
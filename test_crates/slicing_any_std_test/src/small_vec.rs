@@ -0,0 +1,120 @@
+/// Helpers for Inline-based `SliceStorage*`, which spill to `Vec`. Used both by tests in this project, and by tests in `ok_std/` and `no_std_heap/` projects.
+pub mod small_vec {
+    use slicing::abstra::NewEmptyLike;
+    use slicing::packed_bool::PackedBoolStorage;
+    use slicing::slices::{
+        ByteSlice, Slice, SliceBacked, SliceBackedChoice, SliceClone, SliceDefault, SliceRead,
+        SliceStorage, SliceStorageClone, SliceVec, SliceWrite,
+    };
+    #[cfg(feature = "no_std_heap")]
+    extern crate alloc;
+    #[cfg(feature = "no_std_heap")]
+    use alloc::string::{String, ToString};
+
+    /// Generic code grows the buffer, regardless of the current mode.
+    fn append_ramp<S: SliceVec<u32>>(slice: &mut S, count: u32) {
+        for item in 0..count {
+            slice.push(item);
+        }
+    }
+
+    pub fn spill_when_growing() {
+        let mut storage = SliceStorage::<u32, 4>::new_inline();
+        append_ramp(&mut storage, 4);
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Inline);
+        assert_eq!(
+            storage.try_mutable_vec().err(),
+            Some(slicing::Error::NotVecBased)
+        );
+        storage.set(0, &9);
+        assert_eq!(storage.shared_slice(), &[9, 1, 2, 3]);
+
+        storage.push(4);
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Vec);
+        assert_eq!(storage.shared_slice(), &[9, 1, 2, 3, 4]);
+
+        // It stays Vec-based when shrinking.
+        storage.truncate(2);
+        storage.shrink_to_fit();
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Vec);
+        assert_eq!(storage.shared_slice(), &[9, 1]);
+
+        let mut other = SliceStorage::<u32, 4>::from_slice_to_inline(&[0, 1]);
+        other.extend_from_slice(&[5, 6, 7]);
+        assert_eq!(other.backed_choice(), SliceBackedChoice::Vec);
+        assert_eq!(other.len(), 5);
+        assert_eq!(
+            SliceStorage::<u32, 1>::from_slice_to_inline(&[0, 1]).backed_choice(),
+            SliceBackedChoice::Vec
+        );
+    }
+
+    pub fn other_growable_operations() {
+        let mut storage = SliceStorage::<u32, 3>::from_slice_to_inline(&[1, 2]);
+        storage.insert(0, 0);
+        assert_eq!(storage.remove(2), 2);
+        assert_eq!(storage.pop(), Some(1));
+        storage.resize(3, 9);
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Inline);
+        assert_eq!(
+            storage.try_insert(5, 0),
+            Err(slicing::Error::IndexOutOfBounds { index: 5, len: 3 })
+        );
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Inline);
+        storage.insert(3, 8);
+        assert_eq!(storage.backed_choice(), SliceBackedChoice::Vec);
+        assert_eq!(storage.shared_slice(), &[0, 9, 9, 8]);
+
+        let mut reserved = SliceStorage::<u32, 3>::new_inline();
+        reserved.reserve(2);
+        assert_eq!(reserved.backed_choice(), SliceBackedChoice::Inline);
+        reserved.reserve(4);
+        assert!(reserved.mutable_vec().capacity() >= 4);
+
+        // Content-based comparison and owned iteration, regardless of mode.
+        let inline = SliceStorage::<u32, 8>::from_slice_to_inline(&[0, 9, 9, 8]);
+        assert_eq!(storage, inline);
+        assert!(inline.clone().into_iter().rev().eq([8, 9, 9, 0]));
+        assert_eq!(
+            inline.to_vec_based().backed_choice(),
+            SliceBackedChoice::Vec
+        );
+    }
+
+    /// `SliceBackedChoice::Inline` works like the other owned choices.
+    pub fn choice_constructors() {
+        let zeros = ByteSlice::<4>::from_default(3, SliceBackedChoice::Inline);
+        assert_eq!(zeros.backed_choice(), SliceBackedChoice::Inline);
+        assert_eq!(zeros.shared_slice(), &[0, 0, 0]);
+        assert_eq!(
+            ByteSlice::<4>::from_default(5, SliceBackedChoice::Inline).backed_choice(),
+            SliceBackedChoice::Vec
+        );
+
+        // Item types without `Default`, too.
+        let sevens = SliceStorage::<u32, 2>::from_value(&7, 2, SliceBackedChoice::Inline);
+        assert_eq!(sevens.backed_choice(), SliceBackedChoice::Inline);
+        assert_eq!(sevens.shared_slice(), &[7, 7]);
+        let strings = SliceStorageClone::<String, 2>::from_iter_to(
+            ["a", "b", "c"].iter().map(|s| s.to_string()),
+            3,
+            SliceBackedChoice::Inline,
+        );
+        assert_eq!(strings.backed_choice(), SliceBackedChoice::Vec);
+        let narr = SliceStorage::<u32, 0>::from_vec(sevens.shared_slice().to_vec());
+        assert_eq!(
+            SliceStorage::<u32, 2>::from_non_array_vec_based(&narr, &SliceBackedChoice::Inline)
+                .backed_choice(),
+            SliceBackedChoice::Inline
+        );
+        assert_eq!(
+            SliceStorage::<u32, 0>::from_iter_to_inline(core::iter::empty()).backed_choice(),
+            SliceBackedChoice::Inline
+        );
+
+        let bools = PackedBoolStorage::<u8, 0>::from_default(10, SliceBackedChoice::Inline);
+        assert_eq!(bools.len(), 10);
+        assert!(!bools.get(9));
+        assert_eq!(bools.new_empty_like().len(), 0);
+    }
+}
@@ -15,5 +15,6 @@ fn panic(_info: &PanicInfo) -> ! {
 pub extern "C" fn _start() -> ! {
     slicing_any_std_test::slices::bool_slice::construct_from_existing_data();
     slicing_any_std_test::slices::bool_slice::new_contains_initial_false();
+    slicing_any_std_test::array_vec::array_vec::push_pop_insert_remove();
     loop {}
 }
@@ -0,0 +1,30 @@
+#![cfg(test)]
+
+/// Inline fixed-capacity growable storage.
+mod array_vec_tests {
+
+    #[test]
+    fn push_pop_insert_remove() {
+        slicing_any_std_test::array_vec::array_vec::push_pop_insert_remove();
+    }
+
+    #[test]
+    fn resize_and_extend() {
+        slicing_any_std_test::array_vec::array_vec::resize_and_extend();
+    }
+
+    #[test]
+    fn read_write_and_iterate() {
+        slicing_any_std_test::array_vec::array_vec::read_write_and_iterate();
+    }
+
+    #[test]
+    fn owned_iteration() {
+        slicing_any_std_test::array_vec::array_vec::owned_iteration();
+    }
+
+    #[test]
+    fn items_without_default() {
+        slicing_any_std_test::array_vec::array_vec::items_without_default();
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(test)]
+
+/// Copy-on-write storage.
+mod cow_tests {
+
+    #[test]
+    fn borrows_until_modified() {
+        slicing_any_std_test::cow::cow::borrows_until_modified();
+    }
+
+    #[test]
+    fn clone_shared() {
+        slicing_any_std_test::cow::cow::clone_shared();
+    }
+
+    #[test]
+    fn make_mut() {
+        slicing_any_std_test::cow::cow::make_mut();
+    }
+}
@@ -1,5 +1,12 @@
 #![no_std]
 
+mod array_vec;
+mod byte_slice;
+mod cow;
+mod error;
+mod packed_bool;
+mod packed_uint;
+mod simd;
 mod slices;
 
 // TODO test that the following fails to compile
@@ -0,0 +1,20 @@
+#![cfg(test)]
+
+/// Packed fixed-width unsigned integer storage.
+mod uint_storage_tests {
+
+    #[test]
+    fn array_get_set() {
+        slicing_any_std_test::packed_uint::uint_storage::array_get_set();
+    }
+
+    #[test]
+    fn wide_values() {
+        slicing_any_std_test::packed_uint::uint_storage::wide_values();
+    }
+
+    #[test]
+    fn byte_slice_variants() {
+        slicing_any_std_test::packed_uint::uint_storage::byte_slice_variants();
+    }
+}
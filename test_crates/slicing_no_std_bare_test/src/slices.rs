@@ -12,4 +12,47 @@ mod bool_slice_tests {
     fn new_contains_initial_false() {
         slicing_any_std_test::slices::bool_slice::new_contains_initial_false();
     }
+
+    #[test]
+    fn read_and_write_capabilities() {
+        slicing_any_std_test::slices::bool_slice::read_and_write_capabilities();
+    }
+
+    #[test]
+    fn iter_mut() {
+        slicing_any_std_test::slices::bool_slice::iter_mut();
+    }
+}
+
+/// Standard trait implementations of `SliceStorage*`.
+mod std_traits_tests {
+
+    #[test]
+    fn index_deref_and_iterate() {
+        slicing_any_std_test::slices::std_traits::index_deref_and_iterate();
+    }
+
+    #[test]
+    fn content_based_comparison() {
+        slicing_any_std_test::slices::std_traits::content_based_comparison();
+    }
+}
+
+/// Sub-slice views of `SliceView`.
+mod views_tests {
+
+    #[test]
+    fn view_and_view_mut() {
+        slicing_any_std_test::slices::views::view_and_view_mut();
+    }
+
+    #[test]
+    fn split_at_mut() {
+        slicing_any_std_test::slices::views::split_at_mut();
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        slicing_any_std_test::slices::views::chunks_and_windows();
+    }
 }
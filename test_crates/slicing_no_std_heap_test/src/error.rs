@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+/// Fallible (`try_*`) API.
+mod fallible_tests {
+
+    #[test]
+    fn get_and_set() {
+        slicing_any_std_test::error::fallible::get_and_set();
+    }
+
+    #[test]
+    fn constructors() {
+        slicing_any_std_test::error::fallible::constructors();
+    }
+
+    #[test]
+    fn packed() {
+        slicing_any_std_test::error::fallible::packed();
+    }
+
+    #[test]
+    fn vec_based() {
+        slicing_any_std_test::error::fallible::vec_based();
+    }
+}
@@ -1,3 +1,13 @@
 #![no_std]
 
+mod array_vec;
+mod byte_slice;
+mod cow;
+mod error;
+mod packed_bool;
+mod packed_uint;
+mod roaring;
+mod run_length;
+mod simd;
 mod slices;
+mod small_vec;
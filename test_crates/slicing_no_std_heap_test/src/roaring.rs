@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+/// Compressed (Roaring-style) bool storage.
+mod bool_storage_tests {
+
+    #[test]
+    fn get_set_and_iter() {
+        slicing_any_std_test::roaring::bool_storage::get_set_and_iter();
+    }
+
+    #[test]
+    fn sparse_and_clustered() {
+        slicing_any_std_test::roaring::bool_storage::sparse_and_clustered();
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        slicing_any_std_test::roaring::bool_storage::union_and_intersection();
+    }
+
+    #[test]
+    fn conversions() {
+        slicing_any_std_test::roaring::bool_storage::conversions();
+    }
+}
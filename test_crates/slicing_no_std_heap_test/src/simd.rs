@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+/// SIMD-accelerated bulk operations.
+mod bulk_ops_tests {
+
+    #[test]
+    fn fill_find_and_count() {
+        slicing_any_std_test::simd::bulk_ops::fill_find_and_count();
+    }
+
+    #[test]
+    fn compare_and_bitwise() {
+        slicing_any_std_test::simd::bulk_ops::compare_and_bitwise();
+    }
+
+    #[test]
+    fn packed_bool_fill_and_eq() {
+        slicing_any_std_test::simd::bulk_ops::packed_bool_fill_and_eq();
+    }
+
+    #[test]
+    fn packed_bool_find_and_count() {
+        slicing_any_std_test::simd::bulk_ops::packed_bool_find_and_count();
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(test)]
+
+/// Inline storage that spills to `Vec`.
+mod small_vec_tests {
+
+    #[test]
+    fn spill_when_growing() {
+        slicing_any_std_test::small_vec::small_vec::spill_when_growing();
+    }
+
+    #[test]
+    fn other_growable_operations() {
+        slicing_any_std_test::small_vec::small_vec::other_growable_operations();
+    }
+
+    #[test]
+    fn choice_constructors() {
+        slicing_any_std_test::small_vec::small_vec::choice_constructors();
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(test)]
+
+/// Packed bool storage.
+mod bool_storage_tests {
+
+    #[test]
+    fn from_value_fn_iter_to_array() {
+        slicing_any_std_test::byte_slice::bool_storage::from_value_fn_iter_to_array();
+    }
+
+    #[test]
+    fn len_and_iter_stop_at_n() {
+        slicing_any_std_test::byte_slice::bool_storage::len_and_iter_stop_at_n();
+    }
+
+    #[test]
+    fn from_value_fn_iter_to_vec() {
+        slicing_any_std_test::byte_slice::bool_storage::from_value_fn_iter_to_vec();
+    }
+
+    #[test]
+    fn pack_unpack_array() {
+        slicing_any_std_test::byte_slice::bool_storage::pack_unpack_array();
+    }
+
+    #[test]
+    fn pack_copy_of_fewer_items() {
+        slicing_any_std_test::byte_slice::bool_storage::pack_copy_of_fewer_items();
+    }
+
+    #[test]
+    fn shared_and_mutable_bytes() {
+        slicing_any_std_test::byte_slice::bool_storage::shared_and_mutable_bytes();
+    }
+
+    #[test]
+    fn pack_unpack_vec() {
+        slicing_any_std_test::byte_slice::bool_storage::pack_unpack_vec();
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg(test)]
+
+/// Copy-on-write storage.
+mod cow_tests {
+
+    #[test]
+    fn borrows_until_modified() {
+        slicing_any_std_test::cow::cow::borrows_until_modified();
+    }
+
+    #[test]
+    fn clone_shared() {
+        slicing_any_std_test::cow::cow::clone_shared();
+    }
+
+    #[test]
+    fn make_mut() {
+        slicing_any_std_test::cow::cow::make_mut();
+    }
+
+    #[test]
+    fn shared_rc() {
+        slicing_any_std_test::cow::cow::shared_rc();
+    }
+}
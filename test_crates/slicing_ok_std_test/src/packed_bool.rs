@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+/// Packed bool storage with non-byte blocks.
+mod bool_storage_tests {
+
+    #[test]
+    fn wide_blocks_get_set() {
+        slicing_any_std_test::packed_bool::bool_storage::wide_blocks_get_set();
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        slicing_any_std_test::packed_bool::bool_storage::bitwise_ops();
+    }
+
+    #[test]
+    fn count_rank_select() {
+        slicing_any_std_test::packed_bool::bool_storage::count_rank_select();
+    }
+
+    #[test]
+    fn iter_ones_zeros_and_search() {
+        slicing_any_std_test::packed_bool::bool_storage::iter_ones_zeros_and_search();
+    }
+
+    #[test]
+    fn range_ops() {
+        slicing_any_std_test::packed_bool::bool_storage::range_ops();
+    }
+
+    #[test]
+    fn msb0_order() {
+        slicing_any_std_test::packed_bool::bool_storage::msb0_order();
+    }
+
+    #[test]
+    fn rank_select_index() {
+        slicing_any_std_test::packed_bool::bool_storage::rank_select_index();
+    }
+
+    #[test]
+    fn bitwise_ops_vec_based() {
+        slicing_any_std_test::packed_bool::bool_storage::bitwise_ops_vec_based();
+    }
+
+    #[test]
+    fn wide_blocks_vec_based() {
+        slicing_any_std_test::packed_bool::bool_storage::wide_blocks_vec_based();
+    }
+
+    #[test]
+    fn atomic_set_clear_toggle() {
+        slicing_any_std_test::packed_bool::bool_storage::atomic_set_clear_toggle();
+    }
+
+    #[test]
+    fn atomic_threads() {
+        slicing_any_std_test::packed_bool::bool_storage::atomic_threads();
+    }
+
+    #[test]
+    fn atomic_vec_based() {
+        slicing_any_std_test::packed_bool::bool_storage::atomic_vec_based();
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(test)]
+
+/// Run-length encoded storage.
+mod storage_tests {
+
+    #[test]
+    fn get_and_iter() {
+        slicing_any_std_test::run_length::storage::get_and_iter();
+    }
+
+    #[test]
+    fn set_splits_and_merges() {
+        slicing_any_std_test::run_length::storage::set_splits_and_merges();
+    }
+
+    #[test]
+    fn slice_storage_conversions() {
+        slicing_any_std_test::run_length::storage::slice_storage_conversions();
+    }
+}
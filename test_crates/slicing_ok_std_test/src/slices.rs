@@ -13,6 +13,16 @@ mod bool_slice_tests {
         slicing_any_std_test::slices::bool_slice::new_contains_initial_false();
     }
 
+    #[test]
+    fn read_and_write_capabilities() {
+        slicing_any_std_test::slices::bool_slice::read_and_write_capabilities();
+    }
+
+    #[test]
+    fn iter_mut() {
+        slicing_any_std_test::slices::bool_slice::iter_mut();
+    }
+
     #[test]
     fn from_vec_etc() {
         slicing_any_std_test::slices::bool_slice::from_vec_etc();
@@ -21,3 +31,89 @@ mod bool_slice_tests {
     #[test]
     fn get() {}
 }
+
+/// Standard trait implementations of `SliceStorage*`.
+mod std_traits_tests {
+
+    #[test]
+    fn index_deref_and_iterate() {
+        slicing_any_std_test::slices::std_traits::index_deref_and_iterate();
+    }
+
+    #[test]
+    fn content_based_comparison() {
+        slicing_any_std_test::slices::std_traits::content_based_comparison();
+    }
+
+    #[test]
+    fn hash_as_key() {
+        slicing_any_std_test::slices::std_traits::hash_as_key();
+    }
+}
+
+/// Growable operations of `SliceVec`.
+mod growable_tests {
+
+    #[test]
+    fn push_pop_insert_remove() {
+        slicing_any_std_test::slices::growable::push_pop_insert_remove();
+    }
+
+    #[test]
+    fn resize_truncate_extend() {
+        slicing_any_std_test::slices::growable::resize_truncate_extend();
+    }
+
+    #[test]
+    fn fixed_size_errors_or_promotes() {
+        slicing_any_std_test::slices::growable::fixed_size_errors_or_promotes();
+    }
+}
+
+/// Sub-slice views of `SliceView`.
+mod views_tests {
+
+    #[test]
+    fn view_and_view_mut() {
+        slicing_any_std_test::slices::views::view_and_view_mut();
+    }
+
+    #[test]
+    fn split_at_mut() {
+        slicing_any_std_test::slices::views::split_at_mut();
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        slicing_any_std_test::slices::views::chunks_and_windows();
+    }
+}
+
+/// Boxed, Rc and Arc variants.
+mod shared_ownership_tests {
+
+    #[test]
+    fn boxed() {
+        slicing_any_std_test::slices::shared_ownership::boxed();
+    }
+
+    #[test]
+    fn rc_and_arc() {
+        slicing_any_std_test::slices::shared_ownership::rc_and_arc();
+    }
+
+    #[test]
+    fn constructors_and_conversions() {
+        slicing_any_std_test::slices::shared_ownership::constructors_and_conversions();
+    }
+
+    #[test]
+    fn collect_pack_and_expand() {
+        slicing_any_std_test::slices::shared_ownership::collect_pack_and_expand();
+    }
+
+    #[test]
+    fn clone_shared() {
+        slicing_any_std_test::slices::shared_ownership::clone_shared();
+    }
+}